@@ -0,0 +1,268 @@
+use std::fmt;
+
+use crate::template::{Diagnostic, PassStyle, Template};
+use crate::{Error, Result};
+
+/// Image files larger than this slow down downloading and updating passes
+pub const MAX_IMAGE_FILE_LEN: usize = 1024 * 1024;
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Kind of an image of a pass, determined by its file name
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ImageKind {
+    /// Artwork of a poster event ticket, `artwork.png`. Available in iOS 18.0.
+    Artwork,
+    /// Image displayed as the background of the front of an event ticket, `background.png`
+    Background,
+    /// Image displayed next to the barcode of a boarding pass, `footer.png`
+    Footer,
+    /// Image displayed in notifications and on the lock screen, `icon.png`. Required.
+    Icon,
+    /// Image displayed in the top left corner of the front of the pass, `logo.png`
+    Logo,
+    /// Logo displayed when the user is asked to sign up for a rewards program,
+    /// `personalizationLogo.png`. Available in iOS 10.0.
+    PersonalizationLogo,
+    /// Image displayed behind the primary fields, `strip.png`
+    Strip,
+    /// Image displayed next to the fields of generic passes and event tickets, `thumbnail.png`
+    Thumbnail,
+}
+
+impl ImageKind {
+    /// All image kinds
+    pub const ALL: [ImageKind; 8] = [
+        ImageKind::Artwork,
+        ImageKind::Background,
+        ImageKind::Footer,
+        ImageKind::Icon,
+        ImageKind::Logo,
+        ImageKind::PersonalizationLogo,
+        ImageKind::Strip,
+        ImageKind::Thumbnail,
+    ];
+
+    /// File name without scale and extension, e.g. `icon`
+    pub fn file_stem(&self) -> &'static str {
+        match self {
+            ImageKind::Artwork => "artwork",
+            ImageKind::Background => "background",
+            ImageKind::Footer => "footer",
+            ImageKind::Icon => "icon",
+            ImageKind::Logo => "logo",
+            ImageKind::PersonalizationLogo => "personalizationLogo",
+            ImageKind::Strip => "strip",
+            ImageKind::Thumbnail => "thumbnail",
+        }
+    }
+
+    /// Image kind of the file name without scale and extension
+    pub fn from_file_stem(file_stem: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|kind| kind.file_stem() == file_stem)
+    }
+
+    /// Maximal size in points recommended by Apple for passes of the given style, `None` if
+    /// Apple does not recommend a size
+    pub fn recommended_size(&self, style: Option<&PassStyle>) -> Option<(u32, u32)> {
+        match self {
+            ImageKind::Artwork => None,
+            ImageKind::Background => Some((180, 220)),
+            ImageKind::Footer => Some((286, 15)),
+            ImageKind::Icon => Some((38, 38)),
+            ImageKind::Logo => Some((160, 50)),
+            ImageKind::PersonalizationLogo => Some((150, 40)),
+            ImageKind::Strip => match style {
+                Some(PassStyle::EventTicket(_)) => Some((375, 98)),
+                Some(PassStyle::Coupon(_)) | Some(PassStyle::StoreCard(_)) => Some((375, 144)),
+                _ => Some((375, 123)),
+            },
+            ImageKind::Thumbnail => Some((90, 90)),
+        }
+    }
+}
+
+impl fmt::Display for ImageKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.file_stem())
+    }
+}
+
+/// Resolution of an image, given as suffix of its file name like `@2x`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ImageScale {
+    /// Standard resolution, without suffix
+    X1,
+    /// Double resolution, `@2x`
+    X2,
+    /// Triple resolution, `@3x`
+    X3,
+}
+
+impl ImageScale {
+    /// Number of pixels per point
+    pub fn factor(&self) -> u32 {
+        match self {
+            ImageScale::X1 => 1,
+            ImageScale::X2 => 2,
+            ImageScale::X3 => 3,
+        }
+    }
+
+    /// Suffix of the file name, e.g. `@2x`
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            ImageScale::X1 => "",
+            ImageScale::X2 => "@2x",
+            ImageScale::X3 => "@3x",
+        }
+    }
+}
+
+/// Image of a pass like `icon@2x.png` or `de.lproj/logo.png`
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ImageAsset {
+    /// Kind of the image
+    pub kind: ImageKind,
+    /// Resolution of the image
+    pub scale: ImageScale,
+    /// Localization folder without `.lproj`, e.g. `de`, `None` for images in the pass root
+    pub localization: Option<String>,
+}
+
+impl ImageAsset {
+    /// Create a new Instance of an image in the pass root
+    pub fn new(kind: ImageKind, scale: ImageScale) -> Self {
+        Self {
+            kind,
+            scale,
+            localization: None,
+        }
+    }
+
+    /// Parse the path of an image relative to the pass root, `None` if it is not a known image
+    pub fn from_name(name: &str) -> Option<Self> {
+        let (localization, file) = match name.split_once('/') {
+            Some((directory, file)) => (Some(directory.strip_suffix(".lproj")?), file),
+            None => (None, name),
+        };
+
+        let file_stem = file.strip_suffix(".png")?;
+        let (file_stem, scale) = match file_stem.split_once('@') {
+            Some((file_stem, "2x")) => (file_stem, ImageScale::X2),
+            Some((file_stem, "3x")) => (file_stem, ImageScale::X3),
+            Some(_) => return None,
+            None => (file_stem, ImageScale::X1),
+        };
+
+        Some(Self {
+            kind: ImageKind::from_file_stem(file_stem)?,
+            scale,
+            localization: localization.map(Into::into),
+        })
+    }
+
+    /// Path of the image relative to the pass root, e.g. `de.lproj/icon@2x.png`
+    pub fn name(&self) -> String {
+        let file = format!("{}{}.png", self.kind.file_stem(), self.scale.suffix());
+
+        match &self.localization {
+            Some(localization) => format!("{}.lproj/{}", localization, file),
+            None => file,
+        }
+    }
+}
+
+/// Read the width and height in pixels from the header of a PNG image
+pub fn png_dimensions(content: &[u8]) -> Result<(u32, u32)> {
+    if content.len() < 24 || !content.starts_with(PNG_SIGNATURE) || &content[12..16] != b"IHDR" {
+        return Err(Error::InvalidAsset("image is not a PNG".into()));
+    }
+
+    let width = u32::from_be_bytes([content[16], content[17], content[18], content[19]]);
+    let height = u32::from_be_bytes([content[20], content[21], content[22], content[23]]);
+
+    Ok((width, height))
+}
+
+/// Check the images of a pass
+///
+/// `assets` are the files of the pass with their path relative to the pass root. Reports a
+/// missing icon and images that are no valid PNG as errors. Warns about images that are not used
+/// by Apple Wallet, that are larger than recommended by Apple for the style of `template` or that
+/// exceed `MAX_IMAGE_FILE_LEN`.
+pub fn validate_images<'a, I>(assets: I, template: &Template) -> Vec<Diagnostic>
+where
+    I: IntoIterator<Item = (&'a str, &'a [u8])>,
+{
+    let style = template.style();
+    let mut diagnostics = Vec::new();
+    let mut has_icon = false;
+
+    for (name, content) in assets {
+        if !name.ends_with(".png") {
+            continue;
+        }
+
+        let image = match ImageAsset::from_name(name) {
+            Some(image) => image,
+            None => {
+                diagnostics.push(Diagnostic::warning(
+                    name,
+                    "image is not used by Apple Wallet",
+                ));
+                continue;
+            }
+        };
+        if image.kind == ImageKind::Icon && image.localization.is_none() {
+            has_icon = true;
+        }
+
+        if content.len() > MAX_IMAGE_FILE_LEN {
+            diagnostics.push(Diagnostic::warning(
+                name,
+                &format!(
+                    "image has {} bytes, images should not exceed {} bytes",
+                    content.len(),
+                    MAX_IMAGE_FILE_LEN
+                ),
+            ));
+        }
+
+        let (width, height) = match png_dimensions(content) {
+            Ok(dimensions) => dimensions,
+            Err(_) => {
+                diagnostics.push(Diagnostic::error(name, "image is not a valid PNG"));
+                continue;
+            }
+        };
+
+        if let Some((max_width, max_height)) = image.kind.recommended_size(style.as_ref()) {
+            let factor = image.scale.factor();
+            if width > max_width * factor || height > max_height * factor {
+                diagnostics.push(Diagnostic::warning(
+                    name,
+                    &format!(
+                        "image is {}x{} pixels, Apple recommends at most {}x{} pixels",
+                        width,
+                        height,
+                        max_width * factor,
+                        max_height * factor
+                    ),
+                ));
+            }
+        }
+    }
+
+    if !has_icon {
+        diagnostics.push(Diagnostic::error(
+            "icon.png",
+            "pass has no icon, Apple Wallet does not install passes without icon",
+        ));
+    }
+
+    diagnostics
+}
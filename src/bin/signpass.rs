@@ -6,7 +6,7 @@ use std::fs::File;
 use std::path::Path;
 use std::process::exit;
 
-use wallet_pass::sign;
+use wallet_pass::sign::{self, FileFilter, SignOptions, SigningIdentity};
 
 /// Sign an apple wallet pass with a given certificate
 #[derive(Debug, Parser)]
@@ -30,6 +30,18 @@ struct Opts {
     /// Force pass signing by removing manifest and signiture if needed
     #[clap(short = 'f', long = "force")]
     force_pass_signing: bool,
+    /// Sign even if a certificate is expired or not yet valid
+    #[clap(long = "allow-expired")]
+    allow_expired_certificates: bool,
+    /// Reject a pass.json with keys that are not known to this tool
+    #[clap(long = "deny-unknown-keys")]
+    deny_unknown_keys: bool,
+    /// Only package files matching this glob pattern, can be given multiple times
+    #[clap(long = "include")]
+    include_patterns: Vec<String>,
+    /// Do not package files matching this glob pattern, can be given multiple times
+    #[clap(long = "exclude")]
+    exclude_patterns: Vec<String>,
 }
 
 pub fn main() {
@@ -46,18 +58,42 @@ pub fn main() {
         format!("{}.pkpass", pass_name)
     };
 
-    let path = Path::new(&output_path);
-    let file = File::create(&path).unwrap();
-    if let Err(e) = sign::sign_path(
-        &opts.pass_path,
-        None,
+    let signing_identity = match SigningIdentity::from_files(
         &opts.certificate_path,
         &opts.certificate_password,
         &opts.wwdr_intermediate_certificate_path,
-        file,
-        opts.force_pass_signing,
     ) {
-        eprintln!("{:?}", e);
+        Ok(signing_identity) => signing_identity,
+        Err(e) => {
+            eprintln!("{}", e);
+            exit(1);
+        }
+    };
+
+    let mut file_filter = FileFilter::new();
+    for pattern in &opts.include_patterns {
+        if let Err(e) = file_filter.include(pattern) {
+            eprintln!("{}", e);
+            exit(1);
+        }
+    }
+    for pattern in &opts.exclude_patterns {
+        if let Err(e) = file_filter.exclude(pattern) {
+            eprintln!("{}", e);
+            exit(1);
+        }
+    }
+
+    let mut options = SignOptions::new();
+    options.force_pass_signing(opts.force_pass_signing);
+    options.allow_expired_certificates(opts.allow_expired_certificates);
+    options.file_filter(file_filter);
+    options.deny_unknown_keys(opts.deny_unknown_keys);
+
+    let path = Path::new(&output_path);
+    let file = File::create(path).unwrap();
+    if let Err(e) = sign::sign_path(&opts.pass_path, None, &signing_identity, file, &options) {
+        eprintln!("{}", e);
         exit(1);
     }
 }
@@ -0,0 +1,231 @@
+use std::error::Error as StdError;
+use std::path::PathBuf;
+use std::{fmt, io};
+
+use crate::sign::certificate::format_time;
+use crate::sign::CertificateInfo;
+use crate::template::Diagnostic;
+
+/// Result type of this crate
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Errors that can occur while loading, signing and verifying passes
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// Reading or writing a file failed
+    Io(io::Error),
+
+    /// A json file could not be parsed or serialized
+    Json {
+        /// Name of the json file, e.g. `pass.json`
+        file: String,
+        /// Underlying error with the line and column of the problem
+        source: serde_json::Error,
+    },
+
+    /// Reading or writing a zip archive failed
+    Zip(zip::result::ZipError),
+
+    /// A certificate or private key could not be loaded or is not usable for signing
+    Certificate(String),
+
+    /// A certificate is expired or not yet valid
+    CertificateExpired(Box<CertificateInfo>),
+
+    /// The password of the PKCS#12 file or of the private key is wrong
+    InvalidPassword,
+
+    /// A required environment variable is not set
+    MissingEnvironmentVariable(String),
+
+    /// The pass directory or archive does not contain a `pass.json`
+    MissingPassJson,
+
+    /// The `pass.json` contains keys that are not known to this crate, given as json paths
+    UnknownKeys(Vec<String>),
+
+    /// `Template::add_extra` was called with a key that is known to this crate
+    KnownKey(String),
+
+    /// The pass archive does not contain a `manifest.json`
+    MissingManifest,
+
+    /// The pass contains `manifest.json` or `signature` which need to be removed before signing
+    SigningArtifactsPresent(Option<PathBuf>),
+
+    /// An identifier of the `pass.json` does not match the signing certificate
+    IdentifierMismatch {
+        /// Json key of the identifier, e.g. `passTypeIdentifier`
+        key: String,
+        /// Value of the `pass.json`, if present as string
+        pass: Option<String>,
+        /// Value of the signing certificate
+        certificate: String,
+    },
+
+    /// A file of the pass is not usable as asset
+    InvalidAsset(String),
+
+    /// A serial number is not usable as file name of a pass archive
+    InvalidSerialNumber(String),
+
+    /// Multiple passes of a batch have the same serial number
+    DuplicateSerialNumber(String),
+
+    /// A date of the pass is not an ISO 8601 date and time with time zone
+    InvalidDate {
+        /// Json path of the date, e.g. `semantics.eventStartDate`
+        key: String,
+        /// Invalid value
+        value: String,
+    },
+
+    /// The `Template` or the images of a pass violate rules of Apple Wallet, contains the
+    /// diagnostics with severity `Error` of `Template::validate` and `asset::validate_images`
+    Validation(Vec<Diagnostic>),
+
+    /// A color is neither a CSS-style RGB triple nor a hex color
+    InvalidColor(String),
+
+    /// A glob pattern of a `FileFilter` could not be parsed
+    InvalidPattern(String),
+
+    /// A cryptographic operation of OpenSSL failed
+    OpenSsl(openssl::error::ErrorStack),
+
+    /// The `ManifestSigner` could not sign the manifest
+    Signing(Box<dyn StdError + Send + Sync>),
+}
+
+impl Error {
+    /// Create an `Error::Json` for the given file
+    pub(crate) fn json(file: &str, source: serde_json::Error) -> Self {
+        Error::Json {
+            file: file.into(),
+            source,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "{}", e),
+            Error::Json { file, source } => write!(f, "Invalid {}: {}", file, source),
+            Error::Zip(e) => write!(f, "Invalid pass archive: {}", e),
+            Error::Certificate(message) => write!(f, "Invalid certificate: {}", message),
+            Error::CertificateExpired(info) => write!(
+                f,
+                "Certificate {} is only valid from {} to {}",
+                info.common_name.as_deref().unwrap_or("without common name"),
+                format_time(info.not_before),
+                format_time(info.not_after)
+            ),
+            Error::InvalidPassword => write!(f, "Invalid certificate password"),
+            Error::MissingEnvironmentVariable(key) => {
+                write!(f, "Environment variable {} is not set", key)
+            }
+            Error::MissingPassJson => write!(f, "Pass does not contain a pass.json"),
+            Error::UnknownKeys(keys) => {
+                write!(f, "pass.json contains unknown keys: {}", keys.join(", "))
+            }
+            Error::KnownKey(key) => write!(
+                f,
+                "{} is known to this crate and has to be set with its field",
+                key
+            ),
+            Error::MissingManifest => write!(f, "Pass archive does not contain a manifest.json"),
+            Error::SigningArtifactsPresent(Some(path)) => write!(
+                f,
+                "{:?} contains pass signing artifacts that need to be removed before signing",
+                path
+            ),
+            Error::SigningArtifactsPresent(None) => write!(
+                f,
+                "Pass contains signing artifacts that need to be removed before signing"
+            ),
+            Error::IdentifierMismatch {
+                key,
+                pass,
+                certificate,
+            } => match pass {
+                Some(pass) => write!(
+                    f,
+                    "{} of pass ({:?}) does not match the signing certificate ({:?})",
+                    key, pass, certificate
+                ),
+                None => write!(
+                    f,
+                    "{} of pass (missing) does not match the signing certificate ({:?})",
+                    key, certificate
+                ),
+            },
+            Error::InvalidAsset(message) => write!(f, "Invalid asset: {}", message),
+            Error::InvalidSerialNumber(serial_number) => write!(
+                f,
+                "Serial number {:?} is not usable as file name",
+                serial_number
+            ),
+            Error::DuplicateSerialNumber(serial_number) => write!(
+                f,
+                "Serial number {:?} is used by multiple passes",
+                serial_number
+            ),
+            Error::InvalidDate { key, value } => write!(
+                f,
+                "Invalid date {:?} for {}, expected an ISO 8601 date like 2024-11-01T18:30:00+01:00",
+                value, key
+            ),
+            Error::Validation(diagnostics) => {
+                write!(f, "Pass violates rules of Apple Wallet:")?;
+                for diagnostic in diagnostics {
+                    write!(f, "\n  {}", diagnostic)?;
+                }
+                Ok(())
+            }
+            Error::InvalidColor(value) => write!(
+                f,
+                "Invalid color {:?}, expected a CSS-style RGB triple like rgb(23, 187, 82)",
+                value
+            ),
+            Error::InvalidPattern(message) => write!(f, "Invalid file pattern {}", message),
+            Error::OpenSsl(e) => write!(f, "{}", e),
+            Error::Signing(e) => write!(f, "Could not sign manifest: {}", e),
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            Error::Json { source, .. } => Some(source),
+            Error::Zip(e) => Some(e),
+            Error::OpenSsl(e) => Some(e),
+            Error::Signing(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<zip::result::ZipError> for Error {
+    fn from(e: zip::result::ZipError) -> Self {
+        match e {
+            zip::result::ZipError::Io(e) => Error::Io(e),
+            e => Error::Zip(e),
+        }
+    }
+}
+
+impl From<openssl::error::ErrorStack> for Error {
+    fn from(e: openssl::error::ErrorStack) -> Self {
+        Error::OpenSsl(e)
+    }
+}
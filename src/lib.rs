@@ -5,6 +5,7 @@
 //! use std::path::Path;
 //! use wallet_pass::{
 //!     template::{Details, Field, Barcode, BarcodeFormat},
+//!     sign::SigningIdentity,
 //!     Pass,
 //! };
 //!
@@ -34,19 +35,27 @@
 //!
 //! pass.store_card(store_card);
 //!
-//! // Sign, comprass and save pass
-//! pass.export_to_file(
+//! // Load certificates once, they can be reused for any number of passes
+//! let signing_identity = SigningIdentity::from_files(
 //!     Path::new("Certificates.p12"),
 //!     "Certificates Password",
 //!     Path::new("Apple Worldwide Developer Relations Certification Authority.pem"),
-//!     Path::new("./StoreCard.pkpass"),
 //! )
 //! .unwrap();
+//!
+//! // Sign, comprass and save pass
+//! pass.export_to_file(&signing_identity, Path::new("./StoreCard.pkpass")).unwrap();
 //! ```
 
 #[cfg(feature = "cli")]
 extern crate clap;
 
+/// Image assets of passes
+pub mod asset;
+
+mod error;
+pub use error::{Error, Result};
+
 mod pass;
 pub use pass::Pass;
 
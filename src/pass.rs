@@ -1,81 +1,397 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs::File;
-use std::io::{self, BufReader, Read, Seek, Write};
+use std::io::{self, BufReader, Cursor, Read, Seek, Write};
 use std::ops::{Deref, DerefMut};
 use std::path::{Path, PathBuf};
+#[cfg(feature = "async")]
+use std::sync::Arc;
+#[cfg(feature = "async")]
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 
-use crate::{sign, template::Template};
+use crate::asset;
+use crate::sign::digest::DigestCache;
+use crate::sign::filter::validate_asset_name;
+use crate::sign::{self, DirectoryFile, ManifestSigner, SignOptions};
+use crate::template::{Diagnostic, LayoutImages, Template};
+use crate::{Error, Result};
+
+mod batch;
+
+/// Hex encoded SHA-1 digests of assets, keyed by their path relative to the pass root
+type AssetDigests = BTreeMap<String, String>;
 
 /// Represents an complete pass with reference to a directory with image and resource files
 #[derive(Debug, Clone)]
 pub struct Pass {
     /// Reference to a directory with image and resource files
-    pass_path: PathBuf,
+    pass_path: Option<PathBuf>,
+    /// Image and resource files held in memory, taking precedence over files in `pass_path`
+    assets: BTreeMap<String, Vec<u8>>,
+    /// Files of `pass_path` that are left out of the pass
+    removed_assets: BTreeSet<String>,
     /// Reference to the `Template` instance
     pub template: Template,
+    /// Options used to sign and package this `Pass`
+    pub options: SignOptions,
+    /// Digests of the assets of previous exports
+    digests: DigestCache,
 }
 
 impl Pass {
     /// Parse a `Pass` instance from the given directory
-    pub fn from_path<P: AsRef<Path>>(pass_path: P) -> io::Result<Self> {
+    pub fn from_path<P: AsRef<Path>>(pass_path: P) -> Result<Self> {
         let path_buf = pass_path.as_ref().join("pass.json");
 
-        let file = File::open(path_buf)?;
+        let file = File::open(path_buf).map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => Error::MissingPassJson,
+            _ => Error::Io(e),
+        })?;
         let mut file_reader = BufReader::new(file);
         let mut file_buffer = Vec::new();
         file_reader.read_to_end(&mut file_buffer)?;
-        let template: crate::template::Template = serde_json::from_slice(&file_buffer)?;
+        let template: crate::template::Template =
+            serde_json::from_slice(&file_buffer).map_err(|e| Error::json("pass.json", e))?;
+
+        Ok(Self {
+            pass_path: Some(pass_path.as_ref().to_path_buf()),
+            assets: BTreeMap::new(),
+            removed_assets: BTreeSet::new(),
+            template,
+            options: SignOptions::default(),
+            digests: DigestCache::default(),
+        })
+    }
+
+    /// Parse a `Pass` instance from a signed pass archive (`.pkpass`)
+    ///
+    /// All files of the archive are held in memory, `manifest.json` and `signature` are dropped so
+    /// the pass can be modified and signed again.
+    pub fn from_pkpass<R: Read + Seek>(reader: R) -> Result<Self> {
+        let mut archive = zip::ZipArchive::new(reader)?;
+
+        let mut template = None;
+        let mut assets = BTreeMap::new();
+        for index in 0..archive.len() {
+            let mut file = archive.by_index(index)?;
+            if file.is_dir() {
+                continue;
+            }
+
+            let mut file_buffer = Vec::new();
+            file.read_to_end(&mut file_buffer)?;
+
+            match file.name() {
+                "pass.json" => {
+                    template = Some(
+                        serde_json::from_slice(&file_buffer)
+                            .map_err(|e| Error::json("pass.json", e))?,
+                    )
+                }
+                "manifest.json" | "signature" => {}
+                name => {
+                    assets.insert(name.to_owned(), file_buffer);
+                }
+            }
+        }
+
+        let template = template.ok_or(Error::MissingPassJson)?;
 
         Ok(Self {
-            pass_path: pass_path.as_ref().to_path_buf(),
+            pass_path: None,
+            assets,
+            removed_assets: BTreeSet::new(),
             template,
+            options: SignOptions::default(),
+            digests: DigestCache::default(),
         })
     }
 
     /// Create a `Pass` instance from the given `Template` and an reference to a directory with image and resource files
     pub fn from_template<P: AsRef<Path>>(template: &Template, pass_path: P) -> Self {
         Self {
-            pass_path: pass_path.as_ref().to_path_buf(),
+            pass_path: Some(pass_path.as_ref().to_path_buf()),
+            assets: BTreeMap::new(),
+            removed_assets: BTreeSet::new(),
             template: template.clone(),
+            options: SignOptions::default(),
+            digests: DigestCache::default(),
+        }
+    }
+
+    /// Add or replace an image or resource file, given as its path relative to the pass root
+    ///
+    /// The file is held in memory and takes precedence over a file with the same name in the pass
+    /// directory, which is left untouched. Use `template` to change the `pass.json`.
+    pub fn set_asset(&mut self, name: &str, content: Vec<u8>) -> Result<()> {
+        if name == "pass.json" {
+            return Err(Error::InvalidAsset(
+                "pass.json is represented by the template".into(),
+            ));
+        }
+        validate_asset_name(name)?;
+
+        self.digests.invalidate(name);
+        self.removed_assets.remove(name);
+        self.assets.insert(name.to_owned(), content);
+
+        Ok(())
+    }
+
+    /// Remove an image or resource file, given as its path relative to the pass root
+    ///
+    /// A file of the pass directory is left out of the pass without deleting it.
+    pub fn remove_asset(&mut self, name: &str) {
+        self.digests.invalidate(name);
+        self.assets.remove(name);
+        if name != "pass.json" {
+            self.removed_assets.insert(name.to_owned());
+        }
+    }
+
+    /// Load all image and resource files of this `Pass`, keyed by their path relative to the pass
+    /// root. Files set with `set_asset` replace files of the pass directory, files removed with
+    /// `remove_asset` are left out. The `pass.json` is represented by `template` and therefore not
+    /// included.
+    pub fn assets(&self) -> Result<BTreeMap<String, Vec<u8>>> {
+        Ok(self.merge_assets(self.read_directory()?))
+    }
+
+    /// Load all image and resource files of this `Pass` like `assets` on the blocking thread pool
+    /// of the tokio runtime
+    #[cfg(feature = "async")]
+    pub async fn assets_async(&self) -> Result<BTreeMap<String, Vec<u8>>> {
+        Ok(self.merge_assets(self.read_directory_async().await?))
+    }
+
+    /// Load all image and resource files of this `Pass` together with their digests
+    fn load_assets(&self) -> Result<(BTreeMap<String, Vec<u8>>, AssetDigests)> {
+        let files = self.read_directory()?;
+        let digests = self.asset_digests(&files);
+
+        Ok((self.merge_assets(files), digests))
+    }
+
+    /// Load the files of the pass directory
+    fn read_directory(&self) -> Result<Vec<DirectoryFile>> {
+        match &self.pass_path {
+            Some(pass_path) => sign::read_pass_directory(pass_path),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Load the files of the pass directory like `read_directory` on the blocking thread pool of
+    /// the tokio runtime
+    #[cfg(feature = "async")]
+    async fn read_directory_async(&self) -> Result<Vec<DirectoryFile>> {
+        let pass_path = match &self.pass_path {
+            Some(pass_path) => pass_path.clone(),
+            None => return Ok(Vec::new()),
+        };
+
+        tokio::task::spawn_blocking(move || sign::read_pass_directory(pass_path))
+            .await
+            .map_err(|e| Error::Io(e.into()))?
+    }
+
+    /// Add the in-memory assets to the files of the pass directory and drop removed files
+    fn merge_assets(&self, files: Vec<DirectoryFile>) -> BTreeMap<String, Vec<u8>> {
+        let mut assets = files
+            .into_iter()
+            .map(|(name, _, content)| (name, content))
+            .collect::<BTreeMap<_, _>>();
+        assets.retain(|name, _| !self.removed_assets.contains(name));
+        assets.extend(self.assets.clone());
+        assets.remove("pass.json");
+
+        assets
+    }
+
+    /// Look up the digests of the assets in the digest cache, only new or changed assets are
+    /// hashed
+    fn asset_digests(&self, files: &[DirectoryFile]) -> AssetDigests {
+        let mut digests = BTreeMap::new();
+
+        for (name, stamp, content) in files {
+            if name != "pass.json"
+                && !self.assets.contains_key(name)
+                && !self.removed_assets.contains(name)
+                && self.options.file_filter.is_included(name)
+            {
+                let digest = self.digests.file_digest(name, *stamp, content);
+                digests.insert(name.clone(), digest);
+            }
+        }
+        for (name, content) in &self.assets {
+            let digest = self.digests.memory_digest(name, content);
+            digests.insert(name.clone(), digest);
         }
+
+        digests
+    }
+
+    /// Check the layout of the pass like `Template::lint_layout`, using the images of this `Pass`
+    pub fn lint_layout(&self) -> Result<Vec<Diagnostic>> {
+        let assets = self.assets()?;
+        let images = LayoutImages::from_asset_names(
+            assets
+                .keys()
+                .filter(|name| self.options.file_filter.is_included(name)),
+        );
+
+        Ok(self.template.lint_layout(&images))
+    }
+
+    /// Check the images of this `Pass` like `asset::validate_images`
+    pub fn validate_images(&self) -> Result<Vec<Diagnostic>> {
+        Ok(self.image_diagnostics(&self.assets()?))
+    }
+
+    /// Check the images of the given assets, skipping files excluded by the file filter
+    fn image_diagnostics(&self, assets: &BTreeMap<String, Vec<u8>>) -> Vec<Diagnostic> {
+        let assets = assets
+            .iter()
+            .filter(|(name, _)| self.options.file_filter.is_included(name))
+            .map(|(name, content)| (name.as_str(), content.as_slice()));
+
+        asset::validate_images(assets, &self.template)
+    }
+
+    /// Check the images of the given assets unless `SignOptions::skip_validation` is set
+    fn validate_assets(&self, assets: &BTreeMap<String, Vec<u8>>) -> Result<()> {
+        if self.options.skip_validation {
+            return Ok(());
+        }
+
+        let errors = self
+            .image_diagnostics(assets)
+            .into_iter()
+            .filter(Diagnostic::is_error)
+            .collect::<Vec<_>>();
+        if !errors.is_empty() {
+            return Err(Error::Validation(errors));
+        }
+
+        Ok(())
+    }
+
+    /// Check the given `Template` with `Template::validate` unless
+    /// `SignOptions::skip_validation` is set. A missing team identifier is not reported if the
+    /// signing certificate provides one.
+    fn validate_template(&self, template: &Template, signer: &dyn ManifestSigner) -> Result<()> {
+        if self.options.skip_validation {
+            return Ok(());
+        }
+
+        let mut errors = template
+            .validate()
+            .into_iter()
+            .filter(Diagnostic::is_error)
+            .collect::<Vec<_>>();
+        if template.team_identifier.is_none()
+            && signer.certificate_info()?.team_identifier.is_some()
+        {
+            errors.retain(|diagnostic| diagnostic.path != "teamIdentifier");
+        }
+
+        if !errors.is_empty() {
+            return Err(Error::Validation(errors));
+        }
+
+        Ok(())
     }
 
     /// Sign, package and save this `Pass` to writer
-    pub fn export<T, P1: AsRef<Path>, P2: AsRef<Path>>(
-        &self,
-        certificate_path: P1,
-        certificate_password: &str,
-        wwdr_intermediate_certificate_path: P2,
-        writer: T,
-    ) -> io::Result<T>
+    ///
+    /// The `Template` is checked with `Template::validate` and the images with
+    /// `asset::validate_images` first, diagnostics with severity `Error` abort the export with
+    /// `Error::Validation`.
+    pub fn export<T>(&self, signer: &dyn ManifestSigner, writer: T) -> Result<T>
     where
         T: Write + Seek,
     {
-        sign::sign_path(
-            &self.pass_path,
+        self.validate_template(&self.template, signer)?;
+        let (files, digests) = self.load_assets()?;
+        self.validate_assets(&files)?;
+
+        sign::sign_files_with_digests(
+            files
+                .iter()
+                .map(|(name, content)| (name.as_str(), content.as_slice())),
+            &digests,
             Some(&self.template),
-            certificate_path,
-            certificate_password,
-            wwdr_intermediate_certificate_path,
+            signer,
             writer,
-            false,
+            &self.options,
         )
     }
 
+    /// Sign, package and save this `Pass` to a writer that does not support seeking, like a
+    /// network stream. The archive is buffered in memory before it is written.
+    pub fn export_to_writer<T>(&self, signer: &dyn ManifestSigner, mut writer: T) -> Result<T>
+    where
+        T: Write,
+    {
+        writer.write_all(&self.export_to_vec(signer)?)?;
+
+        Ok(writer)
+    }
+
+    /// Sign, package and return this `Pass` as bytes
+    pub fn export_to_vec(&self, signer: &dyn ManifestSigner) -> Result<Vec<u8>> {
+        let writer = self.export(signer, Cursor::new(Vec::new()))?;
+
+        Ok(writer.into_inner())
+    }
+
+    /// Sign, package and save this `Pass` to an async writer
+    ///
+    /// Assets are loaded, signed and packaged on the blocking thread pool of the tokio runtime,
+    /// producing the same archive as `export`. The archive is buffered in memory before it is
+    /// written.
+    #[cfg(feature = "async")]
+    pub async fn export_async<T>(&self, signer: Arc<dyn ManifestSigner>, mut writer: T) -> Result<T>
+    where
+        T: AsyncWrite + Unpin,
+    {
+        self.validate_template(&self.template, signer.as_ref())?;
+        let files = self.read_directory_async().await?;
+        let digests = self.asset_digests(&files);
+        let files = self.merge_assets(files);
+        self.validate_assets(&files)?;
+        let template = self.template.clone();
+        let options = self.options.clone();
+
+        let archive = tokio::task::spawn_blocking(move || {
+            let writer = Cursor::new(Vec::new());
+            sign::sign_files_with_digests(
+                files
+                    .iter()
+                    .map(|(name, content)| (name.as_str(), content.as_slice())),
+                &digests,
+                Some(&template),
+                signer.as_ref(),
+                writer,
+                &options,
+            )
+        })
+        .await
+        .map_err(|e| Error::Signing(Box::new(e)))??
+        .into_inner();
+
+        writer.write_all(&archive).await?;
+        writer.flush().await?;
+
+        Ok(writer)
+    }
+
     /// Sign, package and save this `Pass` to a file
-    pub fn export_to_file<P1: AsRef<Path>, P2: AsRef<Path>, P3: AsRef<Path>>(
+    pub fn export_to_file<P: AsRef<Path>>(
         &self,
-        certificate_path: P1,
-        certificate_password: &str,
-        wwdr_intermediate_certificate_path: P2,
-        output_path: P3,
-    ) -> io::Result<()> {
+        signer: &dyn ManifestSigner,
+        output_path: P,
+    ) -> Result<()> {
         let file = File::create(output_path)?;
-        self.export(
-            certificate_path,
-            certificate_password,
-            wwdr_intermediate_certificate_path,
-            file,
-        )?;
+        self.export(signer, file)?;
 
         Ok(())
     }
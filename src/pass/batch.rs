@@ -0,0 +1,118 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+use std::sync::Mutex;
+
+#[cfg(feature = "rayon")]
+use rayon::iter::{ParallelBridge, ParallelIterator};
+
+use super::Pass;
+use crate::sign::{self, ManifestSigner};
+use crate::template::Template;
+use crate::{Error, Result};
+
+impl Pass {
+    /// Sign and package one personalized copy of this `Pass` for every given `Template` mutation
+    ///
+    /// The image and resource files are loaded and hashed once and shared by all copies. Each
+    /// mutation is applied to a copy of `template`, the resulting archive is handed to `sink`
+    /// together with the index of the mutation and the personalized `Template`. With the `rayon`
+    /// feature the passes are signed in parallel and `sink` is called from multiple threads in
+    /// arbitrary order. Every personalized `Template` is validated like in `export`. The export
+    /// stops at the first error.
+    pub fn export_batch<I, M, S>(
+        &self,
+        signer: &dyn ManifestSigner,
+        mutations: I,
+        sink: S,
+    ) -> Result<()>
+    where
+        I: IntoIterator<Item = M>,
+        I::IntoIter: Send,
+        M: FnOnce(&mut Template) + Send,
+        S: Fn(usize, &Template, Vec<u8>) -> Result<()> + Sync,
+    {
+        let (assets, digests) = self.load_assets()?;
+        self.validate_assets(&assets)?;
+
+        let export = |(index, mutation): (usize, M)| {
+            let mut template = self.template.clone();
+            mutation(&mut template);
+            self.validate_template(&template, signer)?;
+
+            let writer = sign::sign_files_with_digests(
+                assets
+                    .iter()
+                    .map(|(name, content)| (name.as_str(), content.as_slice())),
+                &digests,
+                Some(&template),
+                signer,
+                Cursor::new(Vec::new()),
+                &self.options,
+            )?;
+
+            sink(index, &template, writer.into_inner())
+        };
+
+        #[cfg(feature = "rayon")]
+        let result = mutations
+            .into_iter()
+            .enumerate()
+            .par_bridge()
+            .try_for_each(export);
+
+        #[cfg(not(feature = "rayon"))]
+        let result = mutations.into_iter().enumerate().try_for_each(export);
+
+        result
+    }
+
+    /// Sign and package personalized copies of this `Pass` like `export_batch` and save each one
+    /// as `<serial number>.pkpass` to the given directory
+    ///
+    /// Fails with `Error::InvalidSerialNumber` if a serial number is not usable as file name and
+    /// with `Error::DuplicateSerialNumber` if two mutations produce the same serial number,
+    /// instead of overwriting the archive of the earlier one.
+    pub fn export_batch_to_directory<I, M, P>(
+        &self,
+        signer: &dyn ManifestSigner,
+        mutations: I,
+        output_path: P,
+    ) -> Result<()>
+    where
+        I: IntoIterator<Item = M>,
+        I::IntoIter: Send,
+        M: FnOnce(&mut Template) + Send,
+        P: AsRef<Path>,
+    {
+        let output_path = output_path.as_ref();
+        fs::create_dir_all(output_path)?;
+        let serial_numbers = Mutex::new(HashSet::new());
+
+        self.export_batch(signer, mutations, |_, template, archive| {
+            let serial_number = &template.serial_number;
+            if serial_number.is_empty()
+                || serial_number.starts_with('.')
+                || serial_number.contains(['/', '\\'])
+            {
+                return Err(Error::InvalidSerialNumber(serial_number.clone()));
+            }
+
+            let is_new = serial_numbers
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(serial_number.clone());
+            if !is_new {
+                return Err(Error::DuplicateSerialNumber(serial_number.clone()));
+            }
+
+            fs::write(
+                output_path.join(format!("{}.pkpass", serial_number)),
+                archive,
+            )?;
+
+            Ok(())
+        })
+    }
+}
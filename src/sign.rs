@@ -1,94 +1,302 @@
-use fs_extra::dir::CopyOptions;
 use openssl::sha::sha1;
-use openssl::stack::Stack;
 use openssl::x509::X509;
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
 use std::fs::File;
 use std::io::prelude::*;
 use std::io::BufReader;
+use std::io::Cursor;
 use std::io::{Seek, Write};
 use std::iter::Iterator;
-use std::path::{Path, PathBuf};
-use std::{fs, io};
-use tempfile::tempdir;
-use walkdir::{DirEntry, WalkDir};
-use zip::result::ZipError;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+use walkdir::WalkDir;
 use zip::write::FileOptions;
+use zip::DateTime;
 
 use crate::template::Template;
+use crate::{Error, Result};
+use digest::FileStamp;
+
+pub(crate) mod certificate;
+pub use certificate::CertificateInfo;
+
+pub(crate) mod digest;
+
+pub(crate) mod filter;
+pub use filter::{FileFilter, DEFAULT_EXCLUDE_PATTERNS};
+
+mod identity;
+pub use identity::SigningIdentity;
+
+mod options;
+pub use options::SignOptions;
+
+mod verify;
+pub use verify::{verify, VerificationReport};
+
+mod pkcs7;
+
+#[cfg(feature = "pkcs11")]
+mod pkcs11;
+#[cfg(feature = "pkcs11")]
+pub use pkcs11::Pkcs11Signer;
+
+/// Creates the detached PKCS#7 signature of a pass manifest
+///
+/// `SigningIdentity` signs with OpenSSL and a private key held in memory. Implement this trait to
+/// keep the private key in an external system, like a hardware security module or a key
+/// management service.
+pub trait ManifestSigner: Send + Sync {
+    /// Pass type certificate issued by Apple
+    fn certificate(&self) -> &X509;
+
+    /// Apple Worldwide Developer Relations intermediate certificate
+    fn wwdr_intermediate_certificate(&self) -> &X509;
+
+    /// Information about the pass type certificate
+    fn certificate_info(&self) -> Result<CertificateInfo> {
+        CertificateInfo::from_certificate(self.certificate())
+    }
+
+    /// Information about the wwdr intermediate certificate
+    fn wwdr_intermediate_certificate_info(&self) -> Result<CertificateInfo> {
+        CertificateInfo::from_certificate(self.wwdr_intermediate_certificate())
+    }
+
+    /// Check if the pass type certificate or the wwdr intermediate certificate is expired or
+    /// expires within the given duration
+    fn expires_within(&self, duration: Duration) -> Result<bool> {
+        Ok(self.certificate_info()?.expires_within(duration)
+            || self
+                .wwdr_intermediate_certificate_info()?
+                .expires_within(duration))
+    }
+
+    /// Create the DER encoded detached PKCS#7 signature of the manifest. The signature has to
+    /// include the pass type certificate, the wwdr intermediate certificate and the signing
+    /// time. `signing_time` is the pinned `SignOptions::signing_time`, `None` to sign at the
+    /// current time.
+    fn sign_manifest(&self, manifest: &[u8], signing_time: Option<SystemTime>) -> Result<Vec<u8>>;
+}
 
 /// Sign pass with certificates
-pub fn sign_path<T, P1: AsRef<Path>, P2: AsRef<Path>, P3: AsRef<Path>>(
-    pass_path: P1,
+pub fn sign_path<T, P: AsRef<Path>>(
+    pass_path: P,
     template: Option<&Template>,
-    certificate_path: P2,
-    certificate_password: &str,
-    wwdr_intermediate_certificate_path: P3,
+    signer: &dyn ManifestSigner,
     writer: T,
-    force_pass_signing: bool,
-) -> io::Result<T>
+    options: &SignOptions,
+) -> Result<T>
 where
     T: Write + Seek,
 {
-    if force_pass_signing {
+    if options.force_pass_signing {
         force_clean_raw_pass(&pass_path)?;
     }
 
     // Validate that requested contents are not a signed and expanded pass archive.
     validate_directory_as_unsigned_raw_pass(&pass_path)?;
 
-    // Get a temporary place to stash the pass contents
-    let temporary_path = create_temporary_directory()?;
+    // Load the pass contents into memory
+    let files = read_pass_directory(&pass_path)?
+        .into_iter()
+        .map(|(name, _, content)| (name, content));
+
+    sign_files(files, template, signer, writer, options)
+}
+
+/// Sign pass with certificates like `sign_path` and save it to a writer that does not support
+/// seeking, like a network stream. The archive is buffered in memory before it is written.
+pub fn sign_path_to_writer<T, P: AsRef<Path>>(
+    pass_path: P,
+    template: Option<&Template>,
+    signer: &dyn ManifestSigner,
+    mut writer: T,
+    options: &SignOptions,
+) -> Result<T>
+where
+    T: Write,
+{
+    let archive = sign_path(
+        pass_path,
+        template,
+        signer,
+        Cursor::new(Vec::new()),
+        options,
+    )?;
+    writer.write_all(&archive.into_inner())?;
+
+    Ok(writer)
+}
+
+/// Sign pass files held in memory with certificates
+///
+/// Every file is given as its path relative to the pass root, using `/` as separator, and its
+/// content. If a `Template` is given, it replaces the `pass.json` of `files`.
+///
+/// The pass type identifier and team identifier of the `pass.json` have to match the signing
+/// certificate. A missing team identifier is taken from the certificate. Certificates that are
+/// expired or not yet valid, now or at a pinned `SignOptions::signing_time`, are rejected unless
+/// `SignOptions::allow_expired_certificates` is set.
+pub fn sign_files<T, I>(
+    files: I,
+    template: Option<&Template>,
+    signer: &dyn ManifestSigner,
+    writer: T,
+    options: &SignOptions,
+) -> Result<T>
+where
+    T: Write + Seek,
+    I: IntoIterator<Item = (String, Vec<u8>)>,
+{
+    let files = files.into_iter().collect::<Vec<_>>();
+    let files = files
+        .iter()
+        .map(|(name, content)| (name.as_str(), content.as_slice()));
+
+    sign_files_with_digests(files, &BTreeMap::new(), template, signer, writer, options)
+}
+
+/// Sign pass files held in memory like `sign_files` and save the pass to a writer that does not
+/// support seeking, like a network stream. The archive is buffered in memory before it is
+/// written.
+pub fn sign_files_to_writer<T, I>(
+    files: I,
+    template: Option<&Template>,
+    signer: &dyn ManifestSigner,
+    mut writer: T,
+    options: &SignOptions,
+) -> Result<T>
+where
+    T: Write,
+    I: IntoIterator<Item = (String, Vec<u8>)>,
+{
+    let archive = sign_files(files, template, signer, Cursor::new(Vec::new()), options)?;
+    writer.write_all(&archive.into_inner())?;
+
+    Ok(writer)
+}
+
+/// File of a pass with its path relative to the pass root and its content, borrowed from the
+/// caller unless it is generated while signing
+type PassFile<'a> = (&'a str, Cow<'a, [u8]>);
+
+/// Sign pass files held in memory like `sign_files`, reusing known SHA-1 digests
+///
+/// `digests` maps file names to the hex encoded digest of their content. Files without a known
+/// digest and the `pass.json` are hashed. The files are borrowed, so assets shared by many passes
+/// are neither copied nor hashed again.
+pub(crate) fn sign_files_with_digests<'a, T, I>(
+    files: I,
+    digests: &BTreeMap<String, String>,
+    template: Option<&Template>,
+    signer: &dyn ManifestSigner,
+    writer: T,
+    options: &SignOptions,
+) -> Result<T>
+where
+    T: Write + Seek,
+    I: IntoIterator<Item = (&'a str, &'a [u8])>,
+{
+    let certificate_info = signer.certificate_info()?;
+
+    let now = SystemTime::now();
+    let signing_time = options.signing_time.unwrap_or(now);
+
+    // Validate that the certificates are valid right now and at the signing time
+    if !options.allow_expired_certificates {
+        validate_certificate_dates(certificate_info.clone(), &[now, signing_time])?;
+        validate_certificate_dates(
+            signer.wwdr_intermediate_certificate_info()?,
+            &[now, signing_time],
+        )?;
+    }
+
+    // Clean out unneeded files, like .DS_Store files
+    let mut files = files
+        .into_iter()
+        .filter(|(name, _)| options.file_filter.is_included(name))
+        .map(|(name, content)| (name, Cow::Borrowed(content)))
+        .collect::<Vec<PassFile>>();
 
-    // Make a copy of the pass contents to the temporary folder
-    copy_pass_to_temporary_location(&pass_path, &temporary_path)?;
+    // Validate that requested contents are not a signed pass archive.
+    validate_files_as_unsigned_raw_pass(&files)?;
+
+    // Validate that Apple Wallet accepts all files
+    for (name, _) in &files {
+        filter::validate_asset_name(name)?;
+    }
 
     if let Some(template) = template {
-        save_pass_file(template, &temporary_path)?;
+        save_pass_file(template, &mut files)?;
+    }
+
+    // Validate that the pass.json only contains known keys
+    if options.deny_unknown_keys {
+        validate_known_keys(&files)?;
     }
 
-    // Clean out the unneeded .DS_Store files
-    clean_ds_store_files(&temporary_path)?;
+    // Validate that the pass belongs to the signing certificate
+    validate_pass_identifiers(&mut files, &certificate_info)?;
 
     // Build the json manifest
-    let manifest_path = generate_json_manifest(&temporary_path)?;
+    let manifest = generate_json_manifest(&files, digests)?;
 
     // Sign the manifest
-    sign_manifest(
-        certificate_path,
-        certificate_password,
-        wwdr_intermediate_certificate_path,
-        &temporary_path,
-        manifest_path,
-    )?;
+    let signature = signer.sign_manifest(&manifest, options.signing_time)?;
+
+    files.push(("manifest.json", Cow::Owned(manifest)));
+    files.push(("signature", Cow::Owned(signature)));
+
+    // Sort files for a reproducible archive
+    files.sort_by_key(|(name, _)| *name);
 
     // Package pass
-    let writer = compress_pass(&temporary_path, writer)?;
+    compress_pass(&files, writer)
+}
 
-    // Clean up the temp directory
-    delete_temp_dir(&temporary_path)?;
+/// Validate that the certificate is neither expired nor not yet valid at any of the given times
+fn validate_certificate_dates(
+    certificate_info: CertificateInfo,
+    times: &[SystemTime],
+) -> Result<()> {
+    if !times.iter().all(|time| certificate_info.is_valid_at(*time)) {
+        return Err(Error::CertificateExpired(Box::new(certificate_info)));
+    }
 
-    Ok(writer)
+    Ok(())
 }
 
 /// Validate that requested contents are not a signed and expanded pass archive.
-fn validate_directory_as_unsigned_raw_pass<P: AsRef<Path>>(pass_path: P) -> io::Result<()> {
+fn validate_directory_as_unsigned_raw_pass<P: AsRef<Path>>(pass_path: P) -> Result<()> {
     let has_manifest_file = pass_path.as_ref().join("manifest.json").exists();
     let has_signature_file = pass_path.as_ref().join("signature").exists();
 
     if has_manifest_file || has_signature_file {
-        eprintln!(
-            "{:?} contains pass signing artificats that need to be removed before signing.",
-            pass_path.as_ref()
-        );
-        return Err(io::ErrorKind::AlreadyExists.into());
+        return Err(Error::SigningArtifactsPresent(Some(
+            pass_path.as_ref().to_path_buf(),
+        )));
+    }
+
+    Ok(())
+}
+
+/// Validate that requested files do not contain pass signing artifacts.
+fn validate_files_as_unsigned_raw_pass(files: &[PassFile<'_>]) -> Result<()> {
+    if files
+        .iter()
+        .any(|(name, _)| *name == "manifest.json" || *name == "signature")
+    {
+        return Err(Error::SigningArtifactsPresent(None));
     }
 
     Ok(())
 }
 
 /// Remove `manifest.json` and `signature` if they exist
-fn force_clean_raw_pass<P: AsRef<Path>>(pass_path: P) -> io::Result<()> {
+fn force_clean_raw_pass<P: AsRef<Path>>(pass_path: P) -> Result<()> {
     let manifest_file = pass_path.as_ref().join("manifest.json");
     if manifest_file.exists() {
         fs::remove_file(manifest_file)?;
@@ -102,167 +310,158 @@ fn force_clean_raw_pass<P: AsRef<Path>>(pass_path: P) -> io::Result<()> {
     Ok(())
 }
 
-/// Get a temporary place to stash the pass contents
-fn create_temporary_directory() -> io::Result<PathBuf> {
-    Ok(tempdir()?.into_path())
-}
+/// File of the pass directory with its path relative to the pass root, its stamp and its content
+pub(crate) type DirectoryFile = (String, Option<FileStamp>, Vec<u8>);
+
+/// Load all files of the pass directory with their path relative to the pass root
+pub(crate) fn read_pass_directory<P: AsRef<Path>>(pass_path: P) -> Result<Vec<DirectoryFile>> {
+    let mut files = Vec::new();
+
+    for entry in WalkDir::new(&pass_path)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_file())
+    {
+        let file = File::open(entry.path())?;
+        // Read the stamp before the content, so a concurrent change invalidates the stamp
+        let stamp = FileStamp::from_metadata(&file.metadata()?);
+        let mut file_reader = BufReader::new(file);
+        let mut file_buffer = Vec::new();
+        file_reader.read_to_end(&mut file_buffer)?;
 
-/// Make a copy of the pass contents to the temporary folder
-fn copy_pass_to_temporary_location<P1: AsRef<Path>, P2: AsRef<Path>>(
-    pass_path: P1,
-    temporary_path: P2,
-) -> io::Result<()> {
-    let mut options = CopyOptions::new();
-    options.content_only = true;
+        let name = relative_file_name(&pass_path, entry.path())?;
 
-    if fs_extra::dir::copy(pass_path, temporary_path, &options).is_err() {
-        return Err(io::ErrorKind::Other.into());
+        files.push((name, stamp, file_buffer));
     }
 
-    Ok(())
+    Ok(files)
+}
+
+/// Convert the path of a file to its path relative to the pass root, using `/` as separator
+fn relative_file_name<P: AsRef<Path>>(pass_path: P, path: &Path) -> Result<String> {
+    let name = path
+        .strip_prefix(&pass_path)
+        .map_err(|e| Error::InvalidAsset(e.to_string()))?
+        .components()
+        .map(|component| {
+            component.as_os_str().to_str().ok_or_else(|| {
+                Error::InvalidAsset(format!("Could not convert path {:?} to string!", path))
+            })
+        })
+        .collect::<Result<Vec<_>>>()?
+        .join("/");
+
+    Ok(name)
 }
 
 /// Load given `Template` and write content to `pass.json`
-fn save_pass_file<P: AsRef<Path>>(template: &Template, temporary_path: P) -> io::Result<()> {
-    let pass_path = temporary_path.as_ref().join("pass.json");
+fn save_pass_file(template: &Template, files: &mut Vec<PassFile<'_>>) -> Result<()> {
+    let content = serde_json::to_vec_pretty(template).map_err(|e| Error::json("pass.json", e))?;
 
-    let mut pass_file = File::create(pass_path)?;
-    pass_file.write_all(&serde_json::to_vec_pretty(template)?)?;
+    files.retain(|(name, _)| *name != "pass.json");
+    files.push(("pass.json", Cow::Owned(content)));
 
     Ok(())
 }
 
-/// Clean out the unneeded .DS_Store files
-fn clean_ds_store_files<P: AsRef<Path>>(temporary_path: P) -> io::Result<()> {
-    for entry in WalkDir::new(temporary_path)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        if entry.file_name() == ".DS_Store" {
-            fs::remove_file(entry.path())?;
-        }
-    }
+/// Validate that the `pass.json` only contains keys known to `Template`
+fn validate_known_keys(files: &[PassFile<'_>]) -> Result<()> {
+    let (_, content) = files
+        .iter()
+        .find(|(name, _)| *name == "pass.json")
+        .ok_or(Error::MissingPassJson)?;
 
-    Ok(())
-}
-
-/// Build the json manifest
-fn generate_json_manifest<P: AsRef<Path>>(temporary_path: P) -> io::Result<PathBuf> {
-    let mut manifest = HashMap::<String, String>::new();
+    let template: Template =
+        serde_json::from_slice(content).map_err(|e| Error::json("pass.json", e))?;
 
-    for entry in WalkDir::new(&temporary_path)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.path().is_file())
-    {
-        let file = File::open(entry.path())?;
-        let mut file_reader = BufReader::new(file);
-        let mut file_buffer = Vec::new();
-        file_reader.read_to_end(&mut file_buffer)?;
+    template.deny_unknown_keys()
+}
 
-        let digest = sha1(&file_buffer);
-
-        let name = entry
-            .path()
-            .strip_prefix(&temporary_path)
-            .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?
-            .to_str()
-            .ok_or_else(|| {
-                io::Error::new(
-                    io::ErrorKind::Other,
-                    "Could not convert path to string!".to_string(),
-                )
-            })?
-            .to_owned();
-        manifest.insert(name, hex::encode(digest));
+/// Compare pass type identifier and team identifier of `pass.json` with the certificate
+///
+/// A missing team identifier is filled in from the certificate.
+fn validate_pass_identifiers(
+    files: &mut [PassFile<'_>],
+    certificate_info: &CertificateInfo,
+) -> Result<()> {
+    let (_, content) = files
+        .iter_mut()
+        .find(|(name, _)| *name == "pass.json")
+        .ok_or(Error::MissingPassJson)?;
+
+    let mut pass: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_slice(content).map_err(|e| Error::json("pass.json", e))?;
+
+    let identifiers = [
+        ("passTypeIdentifier", &certificate_info.pass_type_identifier),
+        ("teamIdentifier", &certificate_info.team_identifier),
+    ];
+
+    let mut modified = false;
+    for (key, certificate_value) in identifiers {
+        let certificate_value = match certificate_value {
+            Some(certificate_value) => certificate_value,
+            None => continue,
+        };
+
+        match pass.get(key) {
+            Some(serde_json::Value::String(pass_value)) if pass_value == certificate_value => {}
+            None if key == "teamIdentifier" => {
+                pass.insert(key.into(), certificate_value.as_str().into());
+                modified = true;
+            }
+            pass_value => {
+                return Err(Error::IdentifierMismatch {
+                    key: key.into(),
+                    pass: pass_value
+                        .and_then(|value| value.as_str())
+                        .map(str::to_owned),
+                    certificate: certificate_value.clone(),
+                });
+            }
+        }
     }
 
-    let manifest_path = temporary_path.as_ref().join("manifest.json");
-
-    let mut manifest_file = File::create(&manifest_path)?;
-    manifest_file.write_all(serde_json::to_string_pretty(&manifest)?.as_bytes())?;
+    if modified {
+        *content =
+            Cow::Owned(serde_json::to_vec_pretty(&pass).map_err(|e| Error::json("pass.json", e))?);
+    }
 
-    Ok(manifest_path)
+    Ok(())
 }
 
-/// Sign the manifest
-fn sign_manifest<P1: AsRef<Path>, P2: AsRef<Path>, P3: AsRef<Path>, P4: AsRef<Path>>(
-    certificate_path: P1,
-    certificate_password: &str,
-    wwdr_intermediate_certificate_path: P2,
-    temporary_path: P3,
-    manifest_path: P4,
-) -> io::Result<PathBuf> {
-    let pkcs12_file = fs::File::open(certificate_path)?;
-    let mut pkcs12_reader = BufReader::new(pkcs12_file);
-    let mut pkcs12_buffer = Vec::new();
-    pkcs12_reader.read_to_end(&mut pkcs12_buffer)?;
-    let pkcs12_certificate =
-        openssl::pkcs12::Pkcs12::from_der(&pkcs12_buffer)?.parse2(certificate_password)?;
-
-    let x509_file = fs::File::open(wwdr_intermediate_certificate_path)?;
-    let mut x509_reader = BufReader::new(x509_file);
-    let mut x509_buffer = Vec::new();
-    x509_reader.read_to_end(&mut x509_buffer)?;
-    let x509_certificate = openssl::x509::X509::from_pem(&x509_buffer)?;
-
-    let flags = openssl::pkcs7::Pkcs7Flags::BINARY | openssl::pkcs7::Pkcs7Flags::DETACHED;
-
-    let manifest_file = fs::File::open(manifest_path)?;
-    let mut manifest_reader = BufReader::new(manifest_file);
-    let mut manifest_buffer = Vec::new();
-    manifest_reader.read_to_end(&mut manifest_buffer)?;
-
-    let mut certs = Stack::<X509>::new()?;
-    certs.push(x509_certificate)?;
-
-    let signed = openssl::pkcs7::Pkcs7::sign(
-        pkcs12_certificate.cert.as_ref().unwrap(),
-        pkcs12_certificate.pkey.as_ref().unwrap(),
-        &certs,
-        &manifest_buffer,
-        flags,
-    )?;
-
-    let signature_path = temporary_path.as_ref().join("signature");
-
-    let mut signature_file = File::create(&signature_path)?;
-    signature_file.write_all(&signed.to_der()?)?;
+/// Build the json manifest
+fn generate_json_manifest(
+    files: &[PassFile<'_>],
+    digests: &BTreeMap<String, String>,
+) -> Result<Vec<u8>> {
+    let mut manifest = BTreeMap::<&str, String>::new();
+
+    for (name, content) in files {
+        let digest = match digests.get(*name) {
+            Some(digest) if *name != "pass.json" => digest.clone(),
+            _ => hex::encode(sha1(content)),
+        };
+        manifest.insert(*name, digest);
+    }
 
-    Ok(signature_path)
+    serde_json::to_vec_pretty(&manifest).map_err(|e| Error::json("manifest.json", e))
 }
 
 /// Package pass
-fn compress_pass<T, P: AsRef<Path>>(temporary_path: P, writer: T) -> io::Result<T>
+fn compress_pass<T>(files: &[PassFile<'_>], writer: T) -> Result<T>
 where
     T: Write + Seek,
 {
-    if !temporary_path.as_ref().is_dir() {
-        return Err(ZipError::FileNotFound.into());
-    }
-
-    let walkdir = WalkDir::new(&temporary_path);
-    let it = walkdir.into_iter();
-
-    let writer = zip_dir(
-        &mut it.filter_map(|e| e.ok()),
-        &temporary_path,
-        writer,
-        zip::CompressionMethod::Deflated,
-    )?;
+    let writer = zip_files(files, writer, zip::CompressionMethod::Deflated)?;
 
     Ok(writer)
 }
 
-/// Clean up the temp directory
-fn delete_temp_dir<P: AsRef<Path>>(temporary_path: P) -> io::Result<()> {
-    fs::remove_dir_all(temporary_path)
-}
-
-/// Utility function for `compress_pass_file`
-fn zip_dir<T, P: AsRef<Path>>(
-    it: &mut dyn Iterator<Item = DirEntry>,
-    prefix: P,
+/// Utility function for `compress_pass`
+fn zip_files<T>(
+    files: &[PassFile<'_>],
     writer: T,
     method: zip::CompressionMethod,
 ) -> zip::result::ZipResult<T>
@@ -270,33 +469,27 @@ where
     T: Write + Seek,
 {
     let mut zip = zip::ZipWriter::new(writer);
+    // Use a fixed timestamp for a reproducible archive
     let options = FileOptions::default()
         .compression_method(method)
-        .unix_permissions(0o755);
-
-    let mut buffer = Vec::new();
-    for entry in it {
-        let path = entry.path();
-        let name = path
-            .strip_prefix(&prefix)
-            .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+        .unix_permissions(0o755)
+        .last_modified_time(DateTime::default());
 
-        // Write file or directory explicitly
+    let mut directories = HashSet::new();
+    for (name, content) in files {
+        // Write directories explicitly
         // Some unzip tools unzip files with directory paths correctly, some do not!
-        if path.is_file() {
-            #[allow(deprecated)]
-            zip.start_file_from_path(name, options)?;
-            let mut f = File::open(path)?;
-
-            f.read_to_end(&mut buffer)?;
-            zip.write_all(&buffer)?;
-            buffer.clear();
-        } else if !name.as_os_str().is_empty() {
-            // Only if not root! Avoids path spec / warning
-            // and mapname conversion failed error on unzip
-            #[allow(deprecated)]
-            zip.add_directory_from_path(name, options)?;
+        let mut parts = name.split('/').collect::<Vec<_>>();
+        parts.pop();
+        for index in 1..=parts.len() {
+            let directory = parts[..index].join("/");
+            if directories.insert(directory.clone()) {
+                zip.add_directory(directory, options)?;
+            }
         }
+
+        zip.start_file(*name, options)?;
+        zip.write_all(content)?;
     }
     let writer = zip.finish()?;
     Ok(writer)
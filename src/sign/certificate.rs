@@ -0,0 +1,96 @@
+use openssl::asn1::{Asn1Time, Asn1TimeRef};
+use openssl::nid::Nid;
+use openssl::x509::X509Ref;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::Result;
+
+/// Information about a pass type certificate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CertificateInfo {
+    /// Pass type identifier the certificate was issued for, taken from the subject `UID`
+    pub pass_type_identifier: Option<String>,
+
+    /// Team identifier of the certificate owner, taken from the subject `OU`
+    pub team_identifier: Option<String>,
+
+    /// Common name of the subject, e.g. `Pass Type ID: pass.com.example`
+    pub common_name: Option<String>,
+
+    /// Organization name of the subject
+    pub organization_name: Option<String>,
+
+    /// Start of the validity period
+    pub not_before: SystemTime,
+
+    /// End of the validity period
+    pub not_after: SystemTime,
+}
+
+impl CertificateInfo {
+    /// Read the information from the subject and validity period of the given certificate
+    pub fn from_certificate(certificate: &X509Ref) -> Result<Self> {
+        let entry = |nid: Nid| {
+            certificate
+                .subject_name()
+                .entries_by_nid(nid)
+                .next()
+                .and_then(|entry| entry.data().to_string().ok())
+        };
+
+        Ok(Self {
+            pass_type_identifier: entry(Nid::USERID),
+            team_identifier: entry(Nid::ORGANIZATIONALUNITNAME),
+            common_name: entry(Nid::COMMONNAME),
+            organization_name: entry(Nid::ORGANIZATIONNAME),
+            not_before: to_system_time(certificate.not_before())?,
+            not_after: to_system_time(certificate.not_after())?,
+        })
+    }
+
+    /// Check if the certificate is valid at the given point in time
+    pub fn is_valid_at(&self, time: SystemTime) -> bool {
+        self.not_before <= time && time <= self.not_after
+    }
+
+    /// Check if the certificate is expired
+    pub fn is_expired(&self) -> bool {
+        self.not_after < SystemTime::now()
+    }
+
+    /// Check if the certificate is expired or expires within the given duration, e.g. to warn
+    /// about certificates that need to be renewed soon
+    ///
+    /// Durations beyond the representable time are treated as if every certificate expires
+    /// within them.
+    pub fn expires_within(&self, duration: Duration) -> bool {
+        match SystemTime::now().checked_add(duration) {
+            Some(time) => self.not_after < time,
+            None => true,
+        }
+    }
+}
+
+/// Convert an ASN.1 time to `SystemTime`
+fn to_system_time(time: &Asn1TimeRef) -> Result<SystemTime> {
+    let diff = Asn1Time::from_unix(0)?.diff(time)?;
+    let seconds = i64::from(diff.days) * 86_400 + i64::from(diff.secs);
+
+    if seconds >= 0 {
+        Ok(UNIX_EPOCH + Duration::from_secs(seconds as u64))
+    } else {
+        Ok(UNIX_EPOCH - Duration::from_secs(seconds.unsigned_abs()))
+    }
+}
+
+/// Format a point in time like OpenSSL, e.g. `Jan  1 00:00:00 2024 GMT`
+pub(crate) fn format_time(time: SystemTime) -> String {
+    let seconds = match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_secs() as i64,
+        Err(e) => -(e.duration().as_secs() as i64),
+    };
+
+    Asn1Time::from_unix(seconds as _)
+        .map(|time| time.to_string())
+        .unwrap_or_else(|_| format!("{} seconds since 1970", seconds))
+}
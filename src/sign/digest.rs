@@ -0,0 +1,91 @@
+use openssl::sha::sha1;
+use std::collections::HashMap;
+use std::fs::Metadata;
+use std::sync::{Mutex, PoisonError};
+use std::time::SystemTime;
+
+/// Size and modification time of an asset file, used to detect changed files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct FileStamp {
+    len: u64,
+    modified: SystemTime,
+}
+
+impl FileStamp {
+    /// Read the stamp from the metadata of a file. Returns `None` if the platform does not
+    /// provide modification times.
+    pub(crate) fn from_metadata(metadata: &Metadata) -> Option<Self> {
+        Some(FileStamp {
+            len: metadata.len(),
+            modified: metadata.modified().ok()?,
+        })
+    }
+}
+
+/// Origin of a cached digest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DigestKey {
+    /// File of the pass directory with the stamp it had when it was read
+    File(FileStamp),
+    /// Asset held in memory, which is invalidated explicitly when it changes
+    Memory,
+}
+
+/// Cache of the hex encoded SHA-1 digests of asset files, keyed by the asset name
+///
+/// Cloning creates an independent copy of the cache.
+#[derive(Debug, Default)]
+pub(crate) struct DigestCache {
+    digests: Mutex<HashMap<String, (DigestKey, String)>>,
+}
+
+impl DigestCache {
+    /// Digest of a file of the pass directory, the content is only hashed if the stamp changed
+    pub(crate) fn file_digest(
+        &self,
+        name: &str,
+        stamp: Option<FileStamp>,
+        content: &[u8],
+    ) -> String {
+        match stamp {
+            Some(stamp) => self.digest(name, DigestKey::File(stamp), content),
+            None => hex::encode(sha1(content)),
+        }
+    }
+
+    /// Digest of an asset held in memory, the content is only hashed once
+    pub(crate) fn memory_digest(&self, name: &str, content: &[u8]) -> String {
+        self.digest(name, DigestKey::Memory, content)
+    }
+
+    /// Drop the cached digest of an asset, so changed content is hashed again
+    pub(crate) fn invalidate(&self, name: &str) {
+        self.lock().remove(name);
+    }
+
+    fn digest(&self, name: &str, key: DigestKey, content: &[u8]) -> String {
+        if let Some((cached_key, digest)) = self.lock().get(name) {
+            if *cached_key == key {
+                return digest.clone();
+            }
+        }
+
+        // Hash without holding the lock, so assets can be hashed in parallel
+        let digest = hex::encode(sha1(content));
+        self.lock().insert(name.to_owned(), (key, digest.clone()));
+
+        digest
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, (DigestKey, String)>> {
+        self.digests.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Clone for DigestCache {
+    fn clone(&self) -> Self {
+        DigestCache {
+            digests: Mutex::new(self.lock().clone()),
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use glob::{MatchOptions, Pattern};
+
+use crate::{Error, Result};
+
+/// Files that are never meant to be part of a pass, like operating system metadata, editor swap
+/// files, version control folders and documentation
+pub const DEFAULT_EXCLUDE_PATTERNS: &[&str] = &[
+    ".DS_Store",
+    "._*",
+    "Thumbs.db",
+    "desktop.ini",
+    ".git",
+    ".gitignore",
+    ".gitattributes",
+    ".svn",
+    ".hg",
+    "*.swp",
+    "*.swo",
+    "*~",
+    ".#*",
+    "README",
+    "README.*",
+    "LICENSE",
+    "LICENSE.*",
+];
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// Selects the files of a pass directory that are signed and packaged
+///
+/// Patterns are glob patterns. A pattern without `/` matches the name of a file or of any of its
+/// parent directories, e.g. `.git` excludes the whole `.git` folder. A pattern with `/` matches
+/// the path relative to the pass root, e.g. `en.lproj/*.png`. Excluded files are dropped, if
+/// include patterns are given, only files matching at least one of them are kept. The
+/// `pass.json` is always kept.
+#[derive(Debug, Clone)]
+pub struct FileFilter {
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+}
+
+impl FileFilter {
+    /// Create a new Instance that excludes the `DEFAULT_EXCLUDE_PATTERNS`
+    pub fn new() -> Self {
+        let exclude = DEFAULT_EXCLUDE_PATTERNS
+            .iter()
+            .map(|pattern| Pattern::new(pattern).expect("default pattern is valid"))
+            .collect();
+
+        Self {
+            include: Vec::new(),
+            exclude,
+        }
+    }
+
+    /// Create a new Instance that keeps every file
+    pub fn empty() -> Self {
+        Self {
+            include: Vec::new(),
+            exclude: Vec::new(),
+        }
+    }
+
+    /// Only keep files matching this or another include pattern
+    pub fn include(&mut self, pattern: &str) -> Result<()> {
+        self.include.push(parse_pattern(pattern)?);
+        Ok(())
+    }
+
+    /// Drop files matching this pattern
+    pub fn exclude(&mut self, pattern: &str) -> Result<()> {
+        self.exclude.push(parse_pattern(pattern)?);
+        Ok(())
+    }
+
+    /// Check if the file with the given path relative to the pass root is kept
+    pub fn is_included(&self, name: &str) -> bool {
+        if name == "pass.json" {
+            return true;
+        }
+
+        if self.exclude.iter().any(|pattern| matches(pattern, name)) {
+            return false;
+        }
+
+        self.include.is_empty() || self.include.iter().any(|pattern| matches(pattern, name))
+    }
+}
+
+impl Default for FileFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn parse_pattern(pattern: &str) -> Result<Pattern> {
+    Pattern::new(pattern).map_err(|e| Error::InvalidPattern(format!("{}: {}", pattern, e)))
+}
+
+/// Match the full path for patterns with a separator, otherwise every path component
+fn matches(pattern: &Pattern, name: &str) -> bool {
+    if pattern.as_str().contains('/') {
+        pattern.matches_with(name, MATCH_OPTIONS)
+    } else {
+        name.split('/')
+            .any(|component| pattern.matches_with(component, MATCH_OPTIONS))
+    }
+}
+
+/// Validate that Apple Wallet accepts the file with the given path relative to the pass root
+///
+/// Passes may only contain the `pass.json`, the `personalization.json`, PNG images and
+/// localization folders (`<language>.lproj`) with a `pass.strings` file and PNG images.
+pub(crate) fn validate_asset_name(name: &str) -> Result<()> {
+    let is_png = |file: &str| file.len() > ".png".len() && file.ends_with(".png");
+
+    let accepted = match name.split('/').collect::<Vec<_>>().as_slice() {
+        [file] => *file == "pass.json" || *file == "personalization.json" || is_png(file),
+        [directory, file] => {
+            directory.len() > ".lproj".len()
+                && directory.ends_with(".lproj")
+                && (*file == "pass.strings" || is_png(file))
+        }
+        _ => false,
+    };
+
+    if !accepted {
+        return Err(Error::InvalidAsset(format!(
+            "{} is not accepted by Apple Wallet",
+            name
+        )));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,283 @@
+use openssl::error::ErrorStack;
+use openssl::hash::MessageDigest;
+use openssl::pkcs7::{Pkcs7, Pkcs7Flags};
+use openssl::pkey::{PKey, Private};
+use openssl::sign::Signer;
+use openssl::stack::Stack;
+use openssl::x509::{X509VerifyResult, X509};
+use std::io::prelude::*;
+use std::io::BufReader;
+use std::path::Path;
+use std::time::SystemTime;
+use std::{env, fs};
+
+use super::{pkcs7, ManifestSigner};
+use crate::{Error, Result};
+
+/// Certificate, private key and wwdr intermediate certificate used to sign passes
+///
+/// The certificates are loaded once and can be shared between threads to sign any number of
+/// passes.
+#[derive(Debug, Clone)]
+pub struct SigningIdentity {
+    /// Pass type certificate issued by Apple
+    certificate: X509,
+    /// Private key belonging to the pass type certificate
+    private_key: PKey<Private>,
+    /// Apple Worldwide Developer Relations intermediate certificate
+    wwdr_intermediate_certificate: X509,
+}
+
+impl SigningIdentity {
+    /// Create a new Instance
+    ///
+    /// Fails if the private key does not belong to the certificate or the certificate was not
+    /// issued by the wwdr intermediate certificate.
+    pub fn new(
+        certificate: X509,
+        private_key: PKey<Private>,
+        wwdr_intermediate_certificate: X509,
+    ) -> Result<Self> {
+        if !certificate.public_key()?.public_eq(&private_key) {
+            return Err(Error::Certificate(
+                "Private key does not belong to the certificate".into(),
+            ));
+        }
+
+        validate_certificate_issuer(&certificate, &wwdr_intermediate_certificate)?;
+
+        Ok(Self {
+            certificate,
+            private_key,
+            wwdr_intermediate_certificate,
+        })
+    }
+
+    /// Load a `SigningIdentity` from a DER encoded PKCS#12 file and a PEM or DER encoded wwdr
+    /// intermediate certificate
+    pub fn from_files<P1: AsRef<Path>, P2: AsRef<Path>>(
+        certificate_path: P1,
+        certificate_password: &str,
+        wwdr_intermediate_certificate_path: P2,
+    ) -> Result<Self> {
+        let pkcs12_buffer = read_file(certificate_path)?;
+        let x509_buffer = read_file(wwdr_intermediate_certificate_path)?;
+
+        Self::from_pkcs12(&pkcs12_buffer, certificate_password, &x509_buffer)
+    }
+
+    /// Load a `SigningIdentity` from DER encoded PKCS#12 bytes and a PEM or DER encoded wwdr
+    /// intermediate certificate
+    pub fn from_pkcs12(
+        pkcs12: &[u8],
+        certificate_password: &str,
+        wwdr_intermediate_certificate: &[u8],
+    ) -> Result<Self> {
+        let pkcs12_certificate = openssl::pkcs12::Pkcs12::from_der(pkcs12)
+            .map_err(|e| certificate_error("Could not parse PKCS#12", e))?
+            .parse2(certificate_password)
+            .map_err(|e| certificate_error("Could not decrypt PKCS#12", e))?;
+
+        // Without a private key, OpenSSL returns the certificates in `ca` instead of `cert`
+        let private_key = pkcs12_certificate
+            .pkey
+            .ok_or_else(|| Error::Certificate("PKCS#12 does not contain a private key".into()))?;
+        let certificate = pkcs12_certificate
+            .cert
+            .ok_or_else(|| Error::Certificate("PKCS#12 does not contain a certificate".into()))?;
+
+        Self::new(
+            certificate,
+            private_key,
+            parse_certificate(wwdr_intermediate_certificate)?,
+        )
+    }
+
+    /// Load a `SigningIdentity` from a PEM encoded certificate, a PEM encoded private key and a
+    /// PEM or DER encoded wwdr intermediate certificate
+    ///
+    /// If the private key is encrypted, `private_key_password` is used to decrypt it.
+    pub fn from_pem(
+        certificate: &[u8],
+        private_key: &[u8],
+        private_key_password: Option<&str>,
+        wwdr_intermediate_certificate: &[u8],
+    ) -> Result<Self> {
+        let private_key = match private_key_password {
+            Some(password) => {
+                PKey::private_key_from_pem_passphrase(private_key, password.as_bytes())
+            }
+            None => PKey::private_key_from_pem(private_key),
+        }
+        .map_err(|e| certificate_error("Could not parse private key", e))?;
+
+        Self::new(
+            parse_certificate(certificate)?,
+            private_key,
+            parse_certificate(wwdr_intermediate_certificate)?,
+        )
+    }
+
+    /// Load a `SigningIdentity` from environment variables
+    ///
+    /// The pass type certificate is read either from `WALLET_PASS_PKCS12` (base64 encoded
+    /// PKCS#12) with `WALLET_PASS_PKCS12_PASSWORD`, or from `WALLET_PASS_CERTIFICATE` and
+    /// `WALLET_PASS_PRIVATE_KEY` (PEM) with the optional `WALLET_PASS_PRIVATE_KEY_PASSWORD`.
+    /// `WALLET_PASS_WWDR_CERTIFICATE` contains the wwdr intermediate certificate, either PEM
+    /// encoded or base64 encoded DER.
+    pub fn from_env() -> Result<Self> {
+        let wwdr_intermediate_certificate = read_env_certificate("WALLET_PASS_WWDR_CERTIFICATE")?;
+
+        if let Ok(pkcs12) = env::var("WALLET_PASS_PKCS12") {
+            let pkcs12 = decode_base64("WALLET_PASS_PKCS12", &pkcs12)?;
+            let password = env::var("WALLET_PASS_PKCS12_PASSWORD").unwrap_or_default();
+
+            return Self::from_pkcs12(&pkcs12, &password, &wwdr_intermediate_certificate);
+        }
+
+        let certificate = read_env("WALLET_PASS_CERTIFICATE")?;
+        let private_key = read_env("WALLET_PASS_PRIVATE_KEY")?;
+        let private_key_password = env::var("WALLET_PASS_PRIVATE_KEY_PASSWORD").ok();
+
+        Self::from_pem(
+            certificate.as_bytes(),
+            private_key.as_bytes(),
+            private_key_password.as_deref(),
+            &wwdr_intermediate_certificate,
+        )
+    }
+}
+
+impl ManifestSigner for SigningIdentity {
+    fn certificate(&self) -> &X509 {
+        &self.certificate
+    }
+
+    fn wwdr_intermediate_certificate(&self) -> &X509 {
+        &self.wwdr_intermediate_certificate
+    }
+
+    fn sign_manifest(&self, manifest: &[u8], signing_time: Option<SystemTime>) -> Result<Vec<u8>> {
+        // OpenSSL always signs at the current time, pinned signing times need our own encoder
+        if let Some(signing_time) = signing_time {
+            return pkcs7::sign_detached(
+                &self.certificate,
+                &[&self.certificate, &self.wwdr_intermediate_certificate],
+                manifest,
+                signing_time,
+                |data| {
+                    let mut signer = Signer::new(MessageDigest::sha256(), &self.private_key)?;
+                    signer.update(data)?;
+
+                    Ok(signer.sign_to_vec()?)
+                },
+            );
+        }
+
+        let flags = Pkcs7Flags::BINARY | Pkcs7Flags::DETACHED;
+
+        let mut certs = Stack::<X509>::new()?;
+        certs.push(self.wwdr_intermediate_certificate.clone())?;
+
+        let signed = Pkcs7::sign(
+            &self.certificate,
+            &self.private_key,
+            &certs,
+            manifest,
+            flags,
+        )?;
+
+        Ok(signed.to_der()?)
+    }
+}
+
+/// Validate that the certificate was issued by the wwdr intermediate certificate
+pub(crate) fn validate_certificate_issuer(
+    certificate: &X509,
+    wwdr_intermediate_certificate: &X509,
+) -> Result<()> {
+    let issuer_public_key = wwdr_intermediate_certificate.public_key()?;
+    let is_issued = wwdr_intermediate_certificate.issued(certificate) == X509VerifyResult::OK
+        && certificate.verify(&issuer_public_key)?;
+
+    if !is_issued {
+        return Err(Error::Certificate(
+            "Certificate was not issued by the wwdr intermediate certificate".into(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Read the whole content of a file
+fn read_file<P: AsRef<Path>>(path: P) -> Result<Vec<u8>> {
+    let file = fs::File::open(path)?;
+    let mut reader = BufReader::new(file);
+    let mut buffer = Vec::new();
+    reader.read_to_end(&mut buffer)?;
+
+    Ok(buffer)
+}
+
+/// Marker of PEM encoded data
+const PEM_LABEL: &[u8] = b"-----BEGIN";
+
+/// Check if the given data is PEM encoded
+fn is_pem(data: &[u8]) -> bool {
+    data.windows(PEM_LABEL.len())
+        .any(|window| window == PEM_LABEL)
+}
+
+/// Parse a PEM or DER encoded certificate
+fn parse_certificate(certificate: &[u8]) -> Result<X509> {
+    if is_pem(certificate) {
+        X509::from_pem(certificate)
+    } else {
+        X509::from_der(certificate)
+    }
+    .map_err(|e| certificate_error("Could not parse certificate", e))
+}
+
+/// Reasons of OpenSSL errors caused by a wrong password
+const PASSWORD_ERROR_REASONS: &[&str] = &["mac verify failure", "bad decrypt"];
+
+/// Convert an OpenSSL error while loading a certificate or private key
+fn certificate_error(context: &str, error: ErrorStack) -> Error {
+    let is_password_error = error
+        .errors()
+        .iter()
+        .any(|e| matches!(e.reason(), Some(reason) if PASSWORD_ERROR_REASONS.contains(&reason)));
+
+    if is_password_error {
+        Error::InvalidPassword
+    } else {
+        Error::Certificate(format!("{}: {}", context, error))
+    }
+}
+
+/// Read a required environment variable
+fn read_env(key: &str) -> Result<String> {
+    env::var(key).map_err(|_| Error::MissingEnvironmentVariable(key.into()))
+}
+
+/// Read a PEM or base64 encoded DER certificate from an environment variable
+fn read_env_certificate(key: &str) -> Result<Vec<u8>> {
+    let value = read_env(key)?;
+
+    if is_pem(value.as_bytes()) {
+        Ok(value.into_bytes())
+    } else {
+        decode_base64(key, &value)
+    }
+}
+
+/// Decode base64 content of an environment variable, ignoring whitespace
+fn decode_base64(key: &str, value: &str) -> Result<Vec<u8>> {
+    let value = value
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>();
+
+    openssl::base64::decode_block(&value)
+        .map_err(|e| certificate_error(&format!("{} is not valid base64", key), e))
+}
@@ -0,0 +1,70 @@
+use std::time::SystemTime;
+
+use super::FileFilter;
+
+/// Options for signing and packaging passes
+#[derive(Debug, Clone, Default)]
+pub struct SignOptions {
+    /// Remove `manifest.json` and `signature` from the pass directory before signing
+    pub force_pass_signing: bool,
+
+    /// Sign with certificates that are expired or not yet valid
+    pub allow_expired_certificates: bool,
+
+    /// Signing time embedded in the signature. Defaults to the current time, pin it to create
+    /// byte-identical archives for identical passes. The certificates have to be valid at a
+    /// pinned signing time, too. Pinning the signing time of a `SigningIdentity` requires a
+    /// certificate with an RSA key.
+    pub signing_time: Option<SystemTime>,
+
+    /// Selects the files of the pass that are signed and packaged. Defaults to all files except
+    /// the `DEFAULT_EXCLUDE_PATTERNS`.
+    pub file_filter: FileFilter,
+
+    /// Strict mode: reject a `pass.json` with keys that are not known to this crate
+    pub deny_unknown_keys: bool,
+
+    /// Export passes without checking the `Template` with `Template::validate`
+    pub skip_validation: bool,
+}
+
+impl SignOptions {
+    /// Create a new Instance
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remove `manifest.json` and `signature` from the pass directory before signing
+    pub fn force_pass_signing(&mut self, force_pass_signing: bool) {
+        self.force_pass_signing = force_pass_signing;
+    }
+
+    /// Sign with certificates that are expired or not yet valid
+    pub fn allow_expired_certificates(&mut self, allow_expired_certificates: bool) {
+        self.allow_expired_certificates = allow_expired_certificates;
+    }
+
+    /// Signing time embedded in the signature. Defaults to the current time, pin it to create
+    /// byte-identical archives for identical passes. The certificates have to be valid at a
+    /// pinned signing time, too. Pinning the signing time of a `SigningIdentity` requires a
+    /// certificate with an RSA key.
+    pub fn signing_time(&mut self, signing_time: SystemTime) {
+        self.signing_time = Some(signing_time);
+    }
+
+    /// Selects the files of the pass that are signed and packaged. Defaults to all files except
+    /// the `DEFAULT_EXCLUDE_PATTERNS`.
+    pub fn file_filter(&mut self, file_filter: FileFilter) {
+        self.file_filter = file_filter;
+    }
+
+    /// Strict mode: reject a `pass.json` with keys that are not known to this crate
+    pub fn deny_unknown_keys(&mut self, deny_unknown_keys: bool) {
+        self.deny_unknown_keys = deny_unknown_keys;
+    }
+
+    /// Export passes without checking the `Template` with `Template::validate`
+    pub fn skip_validation(&mut self, skip_validation: bool) {
+        self.skip_validation = skip_validation;
+    }
+}
@@ -0,0 +1,132 @@
+use cryptoki::context::{CInitializeArgs, CInitializeFlags, Pkcs11};
+use cryptoki::error::RvError;
+use cryptoki::mechanism::Mechanism;
+use cryptoki::object::{Attribute, ObjectClass, ObjectHandle};
+use cryptoki::session::{Session, UserType};
+use cryptoki::types::AuthPin;
+use openssl::pkey::Id;
+use openssl::x509::X509;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use super::identity::validate_certificate_issuer;
+use super::{pkcs7, ManifestSigner};
+use crate::{Error, Result};
+
+/// Signs pass manifests with a private key that never leaves a PKCS#11 token, e.g. a hardware
+/// security module or SoftHSM for local testing
+///
+/// The private key has to be an RSA key belonging to the pass type certificate.
+#[derive(Debug)]
+pub struct Pkcs11Signer {
+    /// Logged in session to the token holding the private key
+    session: Mutex<Session>,
+    /// Handle of the private key within the session
+    private_key: ObjectHandle,
+    /// Pass type certificate issued by Apple
+    certificate: X509,
+    /// Apple Worldwide Developer Relations intermediate certificate
+    wwdr_intermediate_certificate: X509,
+}
+
+impl Pkcs11Signer {
+    /// Load the PKCS#11 module at `module_path`, log in to the token labeled `token_label` and
+    /// look up the private key labeled `key_label`
+    ///
+    /// Fails if the certificate was not issued by the wwdr intermediate certificate or does not
+    /// have an RSA key. Multiple signers can use the same module at the same time.
+    pub fn new<P: AsRef<Path>>(
+        module_path: P,
+        token_label: &str,
+        pin: &str,
+        key_label: &str,
+        certificate: X509,
+        wwdr_intermediate_certificate: X509,
+    ) -> Result<Self> {
+        validate_certificate_issuer(&certificate, &wwdr_intermediate_certificate)?;
+        if certificate.public_key()?.id() != Id::RSA {
+            return Err(Error::Certificate(
+                "PKCS#11 signing requires a certificate with an RSA key".into(),
+            ));
+        }
+
+        let pkcs11 = Pkcs11::new(module_path.as_ref()).map_err(signing_error)?;
+        // The module is initialized once per process, another signer may have done that already
+        match pkcs11.initialize(CInitializeArgs::new(CInitializeFlags::OS_LOCKING_OK)) {
+            Ok(())
+            | Err(cryptoki::error::Error::Pkcs11(RvError::CryptokiAlreadyInitialized, _)) => {}
+            Err(e) => return Err(signing_error(e)),
+        }
+
+        let mut token_slot = None;
+        for slot in pkcs11.get_slots_with_token().map_err(signing_error)? {
+            let token_info = pkcs11.get_token_info(slot).map_err(signing_error)?;
+            if token_info.label().trim_end() == token_label {
+                token_slot = Some(slot);
+                break;
+            }
+        }
+        let slot = token_slot.ok_or_else(|| {
+            Error::Signing(format!("No PKCS#11 token labeled {:?} found!", token_label).into())
+        })?;
+
+        let session = pkcs11.open_ro_session(slot).map_err(signing_error)?;
+        session
+            .login(UserType::User, Some(&AuthPin::from(pin)))
+            .map_err(signing_error)?;
+
+        let private_key = session
+            .find_objects(&[
+                Attribute::Class(ObjectClass::PRIVATE_KEY),
+                Attribute::Label(key_label.as_bytes().to_vec()),
+            ])
+            .map_err(signing_error)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                Error::Signing(format!("No private key labeled {:?} found!", key_label).into())
+            })?;
+
+        Ok(Self {
+            session: Mutex::new(session),
+            private_key,
+            certificate,
+            wwdr_intermediate_certificate,
+        })
+    }
+}
+
+impl ManifestSigner for Pkcs11Signer {
+    fn certificate(&self) -> &X509 {
+        &self.certificate
+    }
+
+    fn wwdr_intermediate_certificate(&self) -> &X509 {
+        &self.wwdr_intermediate_certificate
+    }
+
+    fn sign_manifest(&self, manifest: &[u8], signing_time: Option<SystemTime>) -> Result<Vec<u8>> {
+        pkcs7::sign_detached(
+            &self.certificate,
+            &[&self.certificate, &self.wwdr_intermediate_certificate],
+            manifest,
+            signing_time.unwrap_or_else(SystemTime::now),
+            |data| {
+                let session = self
+                    .session
+                    .lock()
+                    .map_err(|e| Error::Signing(e.to_string().into()))?;
+
+                session
+                    .sign(&Mechanism::Sha256RsaPkcs, self.private_key, data)
+                    .map_err(signing_error)
+            },
+        )
+    }
+}
+
+/// Convert a PKCS#11 error
+fn signing_error(error: cryptoki::error::Error) -> Error {
+    Error::Signing(Box::new(error))
+}
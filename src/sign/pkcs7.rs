@@ -0,0 +1,204 @@
+use openssl::pkey::Id;
+use openssl::sha::sha256;
+use openssl::x509::X509;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{Error, Result};
+
+/// Object identifier `1.2.840.113549.1.7.1` (data)
+const OID_DATA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x07, 0x01];
+/// Object identifier `1.2.840.113549.1.7.2` (signedData)
+const OID_SIGNED_DATA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x07, 0x02];
+/// Object identifier `1.2.840.113549.1.9.3` (contentType)
+const OID_CONTENT_TYPE: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x09, 0x03];
+/// Object identifier `1.2.840.113549.1.9.4` (messageDigest)
+const OID_MESSAGE_DIGEST: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x09, 0x04];
+/// Object identifier `1.2.840.113549.1.9.5` (signingTime)
+const OID_SIGNING_TIME: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x09, 0x05];
+/// Object identifier `2.16.840.1.101.3.4.2.1` (sha256)
+const OID_SHA256: &[u8] = &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01];
+/// Object identifier `1.2.840.113549.1.1.1` (rsaEncryption)
+const OID_RSA_ENCRYPTION: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01];
+
+const TAG_INTEGER: u8 = 0x02;
+const TAG_OCTET_STRING: u8 = 0x04;
+const TAG_NULL: u8 = 0x05;
+const TAG_OID: u8 = 0x06;
+const TAG_UTC_TIME: u8 = 0x17;
+const TAG_GENERALIZED_TIME: u8 = 0x18;
+const TAG_SEQUENCE: u8 = 0x30;
+const TAG_SET: u8 = 0x31;
+const TAG_CONTEXT_0: u8 = 0xa0;
+
+/// Build a DER encoded detached PKCS#7 signature of `content`
+///
+/// The signer info carries the content type, message digest and signing time as authenticated
+/// attributes, so the signature is reproducible for a fixed signing time. `sign` has to create
+/// the RSA PKCS#1 v1.5 SHA-256 signature of the given data with the private key belonging to
+/// `certificate`.
+pub(crate) fn sign_detached<F>(
+    certificate: &X509,
+    certificates: &[&X509],
+    content: &[u8],
+    signing_time: SystemTime,
+    sign: F,
+) -> Result<Vec<u8>>
+where
+    F: FnOnce(&[u8]) -> Result<Vec<u8>>,
+{
+    let sha256_algorithm = der(TAG_SEQUENCE, &[der_oid(OID_SHA256), der_null()].concat());
+    if certificate.public_key()?.id() != Id::RSA {
+        return Err(Error::Certificate(
+            "Signing with a pinned signing time requires a certificate with an RSA key".into(),
+        ));
+    }
+    let rsa_algorithm = der(
+        TAG_SEQUENCE,
+        &[der_oid(OID_RSA_ENCRYPTION), der_null()].concat(),
+    );
+
+    let mut attributes = [
+        der_attribute(OID_CONTENT_TYPE, &der_oid(OID_DATA)),
+        der_attribute(OID_SIGNING_TIME, &der_time(signing_time)?),
+        der_attribute(OID_MESSAGE_DIGEST, &der(TAG_OCTET_STRING, &sha256(content))),
+    ];
+    // DER requires the elements of a SET OF to be sorted by their encoding
+    attributes.sort();
+    let attributes = attributes.concat();
+
+    // The signature is calculated over the attributes encoded as an explicit SET OF
+    let signature = sign(&der(TAG_SET, &attributes))?;
+
+    let serial_number = certificate.serial_number().to_bn()?.to_vec();
+    let issuer_and_serial_number = der(
+        TAG_SEQUENCE,
+        &[
+            certificate.issuer_name().to_der()?,
+            der_unsigned_integer(&serial_number),
+        ]
+        .concat(),
+    );
+
+    let signer_info = der(
+        TAG_SEQUENCE,
+        &[
+            der_unsigned_integer(&[1]),
+            issuer_and_serial_number,
+            sha256_algorithm.clone(),
+            der(TAG_CONTEXT_0, &attributes),
+            rsa_algorithm,
+            der(TAG_OCTET_STRING, &signature),
+        ]
+        .concat(),
+    );
+
+    let mut encoded_certificates = Vec::new();
+    for certificate in certificates {
+        encoded_certificates.extend(certificate.to_der()?);
+    }
+
+    let signed_data = der(
+        TAG_SEQUENCE,
+        &[
+            der_unsigned_integer(&[1]),
+            der(TAG_SET, &sha256_algorithm),
+            der(TAG_SEQUENCE, &der_oid(OID_DATA)),
+            der(TAG_CONTEXT_0, &encoded_certificates),
+            der(TAG_SET, &signer_info),
+        ]
+        .concat(),
+    );
+
+    Ok(der(
+        TAG_SEQUENCE,
+        &[der_oid(OID_SIGNED_DATA), der(TAG_CONTEXT_0, &signed_data)].concat(),
+    ))
+}
+
+/// Encode a single DER element
+fn der(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut encoded = vec![tag];
+
+    let length = content.len();
+    if length < 0x80 {
+        encoded.push(length as u8);
+    } else {
+        let bytes = length.to_be_bytes();
+        let skip = bytes.iter().take_while(|b| **b == 0).count();
+        encoded.push(0x80 | (bytes.len() - skip) as u8);
+        encoded.extend(&bytes[skip..]);
+    }
+
+    encoded.extend(content);
+    encoded
+}
+
+/// Encode an object identifier from its encoded content
+fn der_oid(oid: &[u8]) -> Vec<u8> {
+    der(TAG_OID, oid)
+}
+
+/// Encode a `NULL` value
+fn der_null() -> Vec<u8> {
+    der(TAG_NULL, &[])
+}
+
+/// Encode an unsigned big endian number as `INTEGER`
+fn der_unsigned_integer(value: &[u8]) -> Vec<u8> {
+    let skip = value.iter().take_while(|b| **b == 0).count();
+    let mut content = value[skip..].to_vec();
+
+    if !matches!(content.first(), Some(b) if b & 0x80 == 0) {
+        content.insert(0, 0);
+    }
+
+    der(TAG_INTEGER, &content)
+}
+
+/// Encode an authenticated attribute with a single value
+fn der_attribute(oid: &[u8], value: &[u8]) -> Vec<u8> {
+    der(TAG_SEQUENCE, &[der_oid(oid), der(TAG_SET, value)].concat())
+}
+
+/// Encode a point in time as `UTCTime`, or `GeneralizedTime` outside of 1950 to 2049
+fn der_time(time: SystemTime) -> Result<Vec<u8>> {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| Error::Signing(Box::new(e)))?
+        .as_secs();
+
+    let (year, month, day) = civil_from_days((seconds / 86_400) as i64);
+    let seconds_of_day = seconds % 86_400;
+    let time_of_day = format!(
+        "{:02}{:02}{:02}{:02}{:02}Z",
+        month,
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day / 60 % 60,
+        seconds_of_day % 60
+    );
+
+    if (1950..2050).contains(&year) {
+        let content = format!("{:02}{}", year % 100, time_of_day);
+        Ok(der(TAG_UTC_TIME, content.as_bytes()))
+    } else {
+        let content = format!("{:04}{}", year, time_of_day);
+        Ok(der(TAG_GENERALIZED_TIME, content.as_bytes()))
+    }
+}
+
+/// Convert days since the unix epoch to a (year, month, day) date of the gregorian calendar
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    (year, month, day)
+}
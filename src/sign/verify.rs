@@ -0,0 +1,141 @@
+use openssl::error::ErrorStack;
+use openssl::pkcs7::{Pkcs7, Pkcs7Flags};
+use openssl::sha::sha1;
+use openssl::stack::Stack;
+use openssl::x509::store::X509StoreBuilder;
+use openssl::x509::verify::X509VerifyFlags;
+use openssl::x509::{X509PurposeId, X509};
+use std::collections::HashMap;
+use std::io::{Read, Seek};
+
+use crate::{Error, Result};
+
+/// Result of the verification of a signed pass archive
+#[derive(Debug, Clone, Default)]
+pub struct VerificationReport {
+    /// Files listed in `manifest.json` that are missing in the archive
+    pub missing_files: Vec<String>,
+
+    /// Files in the archive that are not listed in `manifest.json`
+    pub extra_files: Vec<String>,
+
+    /// Files whose SHA-1 digest does not match the one in `manifest.json`
+    pub modified_files: Vec<String>,
+
+    /// Reason why the `signature` is missing or invalid, `None` if the signature is valid
+    pub signature_error: Option<String>,
+
+    /// Certificate that signed the manifest
+    pub signer_certificate: Option<X509>,
+}
+
+impl VerificationReport {
+    /// Check if all files match the manifest and the signature is valid
+    pub fn is_valid(&self) -> bool {
+        self.missing_files.is_empty()
+            && self.extra_files.is_empty()
+            && self.modified_files.is_empty()
+            && self.signature_error.is_none()
+    }
+}
+
+/// Verify a signed pass archive
+///
+/// Every file of the archive is checked against `manifest.json` and the `signature` is validated
+/// against the manifest. The certificate chain of the signature has to lead to one of the
+/// `trusted_certificates`, usually the Apple Root CA or the wwdr intermediate certificate.
+pub fn verify<R: Read + Seek>(
+    reader: R,
+    trusted_certificates: &[X509],
+) -> Result<VerificationReport> {
+    let mut archive = zip::ZipArchive::new(reader)?;
+
+    let mut files = HashMap::<String, Vec<u8>>::new();
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index)?;
+        if file.is_dir() {
+            continue;
+        }
+
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer)?;
+        files.insert(file.name().to_owned(), buffer);
+    }
+
+    let manifest_buffer = files
+        .remove("manifest.json")
+        .ok_or(Error::MissingManifest)?;
+    let manifest: HashMap<String, String> =
+        serde_json::from_slice(&manifest_buffer).map_err(|e| Error::json("manifest.json", e))?;
+    let signature = files.remove("signature");
+
+    let mut report = VerificationReport::default();
+
+    for (name, digest) in &manifest {
+        match files.get(name) {
+            Some(content) => {
+                if !hex::encode(sha1(content)).eq_ignore_ascii_case(digest) {
+                    report.modified_files.push(name.clone());
+                }
+            }
+            None => report.missing_files.push(name.clone()),
+        }
+    }
+
+    for name in files.keys() {
+        if !manifest.contains_key(name) {
+            report.extra_files.push(name.clone());
+        }
+    }
+
+    report.missing_files.sort();
+    report.extra_files.sort();
+    report.modified_files.sort();
+
+    match signature {
+        Some(signature) => {
+            match verify_signature(&signature, &manifest_buffer, trusted_certificates) {
+                Ok(signer_certificate) => report.signer_certificate = Some(signer_certificate),
+                Err(e) => report.signature_error = Some(e),
+            }
+        }
+        None => {
+            report.signature_error = Some("Pass archive does not contain a signature!".into());
+        }
+    }
+
+    Ok(report)
+}
+
+/// Validate the detached signature of the manifest and return the signer certificate
+fn verify_signature(
+    signature: &[u8],
+    manifest: &[u8],
+    trusted_certificates: &[X509],
+) -> std::result::Result<X509, String> {
+    let verify = || -> std::result::Result<Option<X509>, ErrorStack> {
+        let pkcs7 = Pkcs7::from_der(signature)?;
+
+        let mut store_builder = X509StoreBuilder::new()?;
+        for certificate in trusted_certificates {
+            store_builder.add_cert(certificate.clone())?;
+        }
+        // Trust anchors are not required to be self signed root certificates
+        store_builder.set_flags(X509VerifyFlags::PARTIAL_CHAIN)?;
+        // Pass type certificates are not issued for S/MIME
+        store_builder.set_purpose(X509PurposeId::ANY)?;
+        let store = store_builder.build();
+
+        let certs = Stack::<X509>::new()?;
+        pkcs7.verify(&certs, &store, Some(manifest), None, Pkcs7Flags::BINARY)?;
+
+        Ok(pkcs7
+            .signers(&certs, Pkcs7Flags::empty())?
+            .into_iter()
+            .next())
+    };
+
+    verify()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Signature does not contain a signer certificate!".to_owned())
+}
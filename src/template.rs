@@ -1,10 +1,44 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+
+use crate::{Error, Result};
+
+mod color;
+mod extra;
+pub use color::Color;
+use extra::ExtraKeys;
+
+#[cfg(feature = "chrono")]
+mod date;
+#[cfg(feature = "chrono")]
+pub use date::{format_date, parse_date};
+
+mod iso8601;
+
+mod layout;
+pub use layout::LayoutImages;
+
+mod style;
+pub use style::PassStyle;
+
+mod validate;
+pub use validate::{Diagnostic, Severity};
 
 /// Apple Wallet pass with localizations, NFC and web service push updates support.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct Template {
+    /// A URL that links to the accessibility information of the venue or the event.
+    /// Available in iOS 18.0.
+    #[serde(rename = "accessibilityURL")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accessibility_url: Option<String>,
+
+    /// A URL that links to experiences that can be added to the pass, like upgrades or add-ons.
+    /// Available in iOS 18.0.
+    #[serde(rename = "addOnURL")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub add_on_url: Option<String>,
+
     /// A URL to be passed to the associated app when launching it. The app receives this URL in
     /// the application:didFinishLaunchingWithOptions: and application:openURL:options: methods
     /// of its app delegate.
@@ -25,10 +59,23 @@ pub struct Template {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub authentication_token: Option<String>,
 
+    /// A list of additional iTunes Store item identifiers for apps associated with the pass.
+    /// They are used after the associated store identifiers.
+    /// Available in iOS 18.0.
+    #[serde(rename = "auxiliaryStoreIdentifiers")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auxiliary_store_identifiers: Option<Vec<f64>>,
+
     /// Background color of the pass, specified as an CSS-style RGB triple.
     #[serde(rename = "backgroundColor")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub background_color: Option<String>,
+    pub background_color: Option<Color>,
+
+    /// A URL that links to the bag policy of the venue of the event.
+    /// Available in iOS 18.0.
+    #[serde(rename = "bagPolicyURL")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bag_policy_url: Option<String>,
 
     /// Information specific to the pass’s barcode.
     /// Deprecated in iOS 9.0 and later; use barcodes instead.
@@ -54,6 +101,24 @@ pub struct Template {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub boarding_pass: Option<BoardingPass>,
 
+    /// The preferred email address to contact the venue, event, or issuer.
+    /// Available in iOS 18.0.
+    #[serde(rename = "contactVenueEmail")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact_venue_email: Option<String>,
+
+    /// The phone number to contact the venue, event, or issuer.
+    /// Available in iOS 18.0.
+    #[serde(rename = "contactVenuePhoneNumber")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact_venue_phone_number: Option<String>,
+
+    /// A URL that links to the website of the venue, event, or issuer.
+    /// Available in iOS 18.0.
+    #[serde(rename = "contactVenueWebsite")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact_venue_website: Option<String>,
+
     /// Information specific to a coupon.
     #[serde(rename = "coupon")]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -66,6 +131,18 @@ pub struct Template {
     #[serde(rename = "description")]
     pub description: String,
 
+    /// A URL that links to directions to the event.
+    /// Available in iOS 18.0.
+    #[serde(rename = "directionsInformationURL")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub directions_information_url: Option<String>,
+
+    /// Text displayed next to the logo on a poster event ticket.
+    /// Available in iOS 18.0.
+    #[serde(rename = "eventLogoText")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_logo_text: Option<String>,
+
     /// Information specific to an event ticket.
     #[serde(rename = "eventTicket")]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -77,10 +154,17 @@ pub struct Template {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub expiration_date: Option<String>,
 
+    /// Background color of the footer of a poster event ticket, specified as a CSS-style RGB
+    /// triple, such as rgb(23, 187, 82).
+    /// Available in iOS 18.0.
+    #[serde(rename = "footerBackgroundColor")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub footer_background_color: Option<Color>,
+
     /// Foreground color of the pass, specified as a CSS-style RGB triple
     #[serde(rename = "foregroundColor")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub foreground_color: Option<String>,
+    pub foreground_color: Option<Color>,
 
     /// Version of the file format.
     #[serde(rename = "formatVersion")]
@@ -106,7 +190,7 @@ pub struct Template {
     /// If omitted, the label color is determined automatically.
     #[serde(rename = "labelColor")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub label_color: Option<String>,
+    pub label_color: Option<Color>,
 
     /// Locations where the pass is relevant. For example, the location of your store.
     #[serde(rename = "locations")]
@@ -127,28 +211,81 @@ pub struct Template {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_distance: Option<f64>,
 
+    /// A URL that links to a site for ordering merchandise for the event.
+    /// Available in iOS 18.0.
+    #[serde(rename = "merchandiseURL")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merchandise_url: Option<String>,
+
     /// Information used for Value Added Service Protocol transactions.
     /// Available in iOS 9.0.
     #[serde(rename = "nfc")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub nfc: Option<Nfc>,
 
+    /// A URL that links to a site for ordering food for the event.
+    /// Available in iOS 18.0.
+    #[serde(rename = "orderFoodURL")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_food_url: Option<String>,
+
     /// Display name of the organization that originated and signed the pass.
     /// Localizable.
     #[serde(rename = "organizationName")]
     pub organization_name: String,
 
+    /// A URL that links to parking information for the event.
+    /// Available in iOS 18.0.
+    #[serde(rename = "parkingInformationURL")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parking_information_url: Option<String>,
+
     /// Pass type identifier, as issued by Apple. The value must correspond with your signing
     /// certificate.
     #[serde(rename = "passTypeIdentifier")]
     pub pass_type_identifier: String,
 
+    /// Styles the pass can be displayed in, in order of preference. A device that does not
+    /// support a style uses the next one, e.g. a poster event ticket falls back to an event
+    /// ticket.
+    /// Available in iOS 18.0.
+    #[serde(rename = "preferredStyleSchemes")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preferred_style_schemes: Option<Vec<StyleScheme>>,
+
+    /// A URL that links to a site to purchase parking for the event.
+    /// Available in iOS 18.0.
+    #[serde(rename = "purchaseParkingURL")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purchase_parking_url: Option<String>,
+
     /// Date and time when the pass becomes relevant. For example, the start time of a movie.
     /// Recommended for event tickets and boarding passes.
     #[serde(rename = "relevantDate")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub relevant_date: Option<String>,
 
+    /// Dates and date intervals when the pass is relevant. Replaces relevantDate on devices that
+    /// support it.
+    /// Available in iOS 18.0.
+    #[serde(rename = "relevantDates")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relevant_dates: Option<Vec<RelevantDate>>,
+
+    /// A URL that links to a site to sell the ticket of the event.
+    /// Available in iOS 18.0.
+    #[serde(rename = "sellURL")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sell_url: Option<String>,
+
+    /// Machine-readable metadata about the pass as a whole, which the system uses to offer the pass
+    /// and related information to the user. Poster event tickets display the event details from
+    /// these semantics.
+    /// Available in iOS 12.0.
+    #[serde(rename = "semantics")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub semantics: Option<Semantics>,
+
     /// Serial number that uniquely identifies the pass. No two passes with the same pass type
     /// identifier may have the same serial number.
     #[serde(rename = "serialNumber")]
@@ -159,6 +296,12 @@ pub struct Template {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub store_card: Option<Details>,
 
+    /// If true, the darkened gradient above the header of a poster event ticket is not displayed.
+    /// Available in iOS 18.0.
+    #[serde(rename = "suppressHeaderDarkening")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suppress_header_darkening: Option<bool>,
+
     /// If true, the strip image is displayed without a shine effect. The default value prior to
     /// iOS 7.0 is false. In iOS 7.0, a shine effect is never applied, and this key is deprecated.
     #[serde(rename = "suppressStripShine")]
@@ -171,6 +314,25 @@ pub struct Template {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub team_identifier: Option<String>,
 
+    /// A URL that links to a site to transfer the ticket of the event.
+    /// Available in iOS 18.0.
+    #[serde(rename = "transferURL")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transfer_url: Option<String>,
+
+    /// A URL that links to information about transit options in the area of the event.
+    /// Available in iOS 18.0.
+    #[serde(rename = "transitInformationURL")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transit_information_url: Option<String>,
+
+    /// If true, Wallet computes the foreground and label colors of a poster event ticket from its
+    /// background image, the foreground color and label color of the pass are ignored.
+    /// Available in iOS 18.0.
+    #[serde(rename = "useAutomaticColors")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub use_automatic_colors: Option<bool>,
+
     /// Custom information for companion apps. This data is not displayed to the user.
     /// For example, a pass for a cafe could include information about the user’s favorite drink
     /// and sandwich in a machine-readable form for the companion app to read, making it easy to
@@ -178,7 +340,7 @@ pub struct Template {
     /// Available in iOS 7.0.
     #[serde(rename = "userInfo")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub user_info: Option<HashMap<String, Option<serde_json::Value>>>,
+    pub user_info: Option<BTreeMap<String, Option<serde_json::Value>>>,
 
     /// Indicates that the pass is void—for example, a one time use coupon that has been
     /// redeemed.
@@ -194,6 +356,13 @@ pub struct Template {
     #[serde(rename = "webServiceURL")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub web_service_url: Option<String>,
+
+    /// Keys of the pass.json that are not known to this crate, e.g. keys added by newer iOS
+    /// versions. They are kept as they are and written back when the pass is exported. Nested
+    /// objects keep their unknown keys in an `extra` field of their own, `unknown_keys` lists
+    /// all of them.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl Template {
@@ -205,40 +374,75 @@ impl Template {
         serial_number: &str,
     ) -> Self {
         Self {
+            accessibility_url: None,
+            add_on_url: None,
             app_launch_url: None,
             associated_store_identifiers: None,
             authentication_token: None,
+            auxiliary_store_identifiers: None,
             background_color: None,
+            bag_policy_url: None,
             barcode: None,
             barcodes: None,
             beacons: None,
             boarding_pass: None,
+            contact_venue_email: None,
+            contact_venue_phone_number: None,
+            contact_venue_website: None,
             coupon: None,
             description: description.into(),
+            directions_information_url: None,
+            event_logo_text: None,
             event_ticket: None,
             expiration_date: None,
+            footer_background_color: None,
             foreground_color: None,
-            format_version: None,
+            format_version: Some(1.into()),
             generic: None,
             grouping_identifier: None,
             label_color: None,
             locations: None,
             logo_text: None,
             max_distance: None,
+            merchandise_url: None,
             nfc: None,
+            order_food_url: None,
             organization_name: organization_name.into(),
+            parking_information_url: None,
             pass_type_identifier: pass_type_identifier.into(),
+            preferred_style_schemes: None,
+            purchase_parking_url: None,
             relevant_date: None,
+            relevant_dates: None,
+            sell_url: None,
+            semantics: None,
             serial_number: serial_number.into(),
             store_card: None,
+            suppress_header_darkening: None,
             suppress_strip_shine: None,
             team_identifier: None,
+            transfer_url: None,
+            transit_information_url: None,
+            use_automatic_colors: None,
             user_info: None,
             voided: None,
             web_service_url: None,
+            extra: serde_json::Map::new(),
         }
     }
 
+    /// A URL that links to the accessibility information of the venue or the event.
+    /// Available in iOS 18.0.
+    pub fn accessibility_url(&mut self, accessibility_url: &str) {
+        self.accessibility_url = Some(accessibility_url.into());
+    }
+
+    /// A URL that links to experiences that can be added to the pass, like upgrades or add-ons.
+    /// Available in iOS 18.0.
+    pub fn add_on_url(&mut self, add_on_url: &str) {
+        self.add_on_url = Some(add_on_url.into());
+    }
+
     /// A URL to be passed to the associated app when launching it. The app receives this URL in
     /// the application:didFinishLaunchingWithOptions: and application:openURL:options: methods
     /// of its app delegate.
@@ -273,9 +477,35 @@ impl Template {
         self.authentication_token = Some(authentication_token.into());
     }
 
+    /// A list of additional iTunes Store item identifiers for apps associated with the pass.
+    /// They are used after the associated store identifiers.
+    /// Available in iOS 18.0.
+    pub fn add_auxiliary_store_identifier(&mut self, auxiliary_store_identifier: f64) {
+        let mut vec = match &self.auxiliary_store_identifiers {
+            Some(vec) => vec.clone(),
+            None => Vec::new(),
+        };
+
+        vec.push(auxiliary_store_identifier);
+        self.auxiliary_store_identifiers = Some(vec);
+    }
+
+    /// A list of additional iTunes Store item identifiers for apps associated with the pass.
+    /// They are used after the associated store identifiers.
+    /// Available in iOS 18.0.
+    pub fn clear_auxiliary_store_identifiers(&mut self) {
+        self.auxiliary_store_identifiers = None;
+    }
+
     /// Background color of the pass, specified as an CSS-style RGB triple.
-    pub fn background_color(&mut self, background_color: &str) {
-        self.background_color = Some(background_color.into());
+    pub fn background_color(&mut self, background_color: Color) {
+        self.background_color = Some(background_color);
+    }
+
+    /// A URL that links to the bag policy of the venue of the event.
+    /// Available in iOS 18.0.
+    pub fn bag_policy_url(&mut self, bag_policy_url: &str) {
+        self.bag_policy_url = Some(bag_policy_url.into());
     }
 
     /// Information specific to the pass’s barcode.
@@ -323,13 +553,33 @@ impl Template {
     }
 
     /// Information specific to a boarding pass.
+    /// Removes all other pass styles, like `set_style`.
     pub fn boarding_pass(&mut self, boarding_pass: BoardingPass) {
-        self.boarding_pass = Some(boarding_pass);
+        self.set_style(PassStyle::BoardingPass(boarding_pass));
+    }
+
+    /// The preferred email address to contact the venue, event, or issuer.
+    /// Available in iOS 18.0.
+    pub fn contact_venue_email(&mut self, contact_venue_email: &str) {
+        self.contact_venue_email = Some(contact_venue_email.into());
+    }
+
+    /// The phone number to contact the venue, event, or issuer.
+    /// Available in iOS 18.0.
+    pub fn contact_venue_phone_number(&mut self, contact_venue_phone_number: &str) {
+        self.contact_venue_phone_number = Some(contact_venue_phone_number.into());
+    }
+
+    /// A URL that links to the website of the venue, event, or issuer.
+    /// Available in iOS 18.0.
+    pub fn contact_venue_website(&mut self, contact_venue_website: &str) {
+        self.contact_venue_website = Some(contact_venue_website.into());
     }
 
     /// Information specific to a coupon.
+    /// Removes all other pass styles, like `set_style`.
     pub fn coupon(&mut self, coupon: Details) {
-        self.coupon = Some(coupon);
+        self.set_style(PassStyle::Coupon(coupon));
     }
 
     /// Brief description of the pass, used by the iOS accessibility technologies.
@@ -340,9 +590,22 @@ impl Template {
         self.description = description.into();
     }
 
+    /// A URL that links to directions to the event.
+    /// Available in iOS 18.0.
+    pub fn directions_information_url(&mut self, directions_information_url: &str) {
+        self.directions_information_url = Some(directions_information_url.into());
+    }
+
+    /// Text displayed next to the logo on a poster event ticket.
+    /// Available in iOS 18.0.
+    pub fn event_logo_text(&mut self, event_logo_text: &str) {
+        self.event_logo_text = Some(event_logo_text.into());
+    }
+
     /// Information specific to an event ticket.
+    /// Removes all other pass styles, like `set_style`.
     pub fn event_ticket(&mut self, event_ticket: Details) {
-        self.event_ticket = Some(event_ticket);
+        self.set_style(PassStyle::EventTicket(event_ticket));
     }
 
     /// Date and time when the pass expires.
@@ -351,9 +614,16 @@ impl Template {
         self.expiration_date = Some(expiration_date.into());
     }
 
+    /// Background color of the footer of a poster event ticket, specified as a CSS-style RGB
+    /// triple, such as rgb(23, 187, 82).
+    /// Available in iOS 18.0.
+    pub fn footer_background_color(&mut self, footer_background_color: Color) {
+        self.footer_background_color = Some(footer_background_color);
+    }
+
     /// Foreground color of the pass, specified as a CSS-style RGB triple
-    pub fn foreground_color(&mut self, foreground_color: &str) {
-        self.foreground_color = Some(foreground_color.into());
+    pub fn foreground_color(&mut self, foreground_color: Color) {
+        self.foreground_color = Some(foreground_color);
     }
 
     /// Version of the file format.
@@ -362,8 +632,9 @@ impl Template {
     }
 
     /// Information specific to a generic pass.
+    /// Removes all other pass styles, like `set_style`.
     pub fn generic(&mut self, generic: Details) {
-        self.generic = Some(generic);
+        self.set_style(PassStyle::Generic(generic));
     }
 
     /// Identifier used to group related passes. If a grouping identifier is specified, passes
@@ -378,8 +649,8 @@ impl Template {
 
     /// olor of the label text, specified as a CSS-style RGB triple.
     /// If omitted, the label color is determined automatically.
-    pub fn label_color(&mut self, label_color: &str) {
-        self.label_color = Some(label_color.into());
+    pub fn label_color(&mut self, label_color: Color) {
+        self.label_color = Some(label_color);
     }
 
     /// Locations where the pass is relevant. For example, the location of your store.
@@ -412,30 +683,110 @@ impl Template {
         self.max_distance = Some(max_distance);
     }
 
+    /// A URL that links to a site for ordering merchandise for the event.
+    /// Available in iOS 18.0.
+    pub fn merchandise_url(&mut self, merchandise_url: &str) {
+        self.merchandise_url = Some(merchandise_url.into());
+    }
+
     /// Information used for Value Added Service Protocol transactions.
     /// Available in iOS 9.0.
     pub fn nfc(&mut self, nfc: Nfc) {
         self.nfc = Some(nfc);
     }
 
+    /// A URL that links to a site for ordering food for the event.
+    /// Available in iOS 18.0.
+    pub fn order_food_url(&mut self, order_food_url: &str) {
+        self.order_food_url = Some(order_food_url.into());
+    }
+
     /// Display name of the organization that originated and signed the pass.
     /// Localizable.
     pub fn organization_name(&mut self, organization_name: &str) {
         self.organization_name = organization_name.into();
     }
 
+    /// A URL that links to parking information for the event.
+    /// Available in iOS 18.0.
+    pub fn parking_information_url(&mut self, parking_information_url: &str) {
+        self.parking_information_url = Some(parking_information_url.into());
+    }
+
     /// Pass type identifier, as issued by Apple. The value must correspond with your signing
     /// certificate.
     pub fn pass_type_identifier(&mut self, pass_type_identifier: &str) {
         self.pass_type_identifier = pass_type_identifier.into();
     }
 
+    /// Styles the pass can be displayed in, in order of preference. A device that does not
+    /// support a style uses the next one, e.g. a poster event ticket falls back to an event
+    /// ticket.
+    /// Available in iOS 18.0.
+    pub fn add_preferred_style_scheme(&mut self, preferred_style_scheme: StyleScheme) {
+        let mut vec = match &self.preferred_style_schemes {
+            Some(vec) => vec.clone(),
+            None => Vec::new(),
+        };
+
+        vec.push(preferred_style_scheme);
+        self.preferred_style_schemes = Some(vec);
+    }
+
+    /// Styles the pass can be displayed in, in order of preference. A device that does not
+    /// support a style uses the next one, e.g. a poster event ticket falls back to an event
+    /// ticket.
+    /// Available in iOS 18.0.
+    pub fn clear_preferred_style_schemes(&mut self) {
+        self.preferred_style_schemes = None;
+    }
+
+    /// A URL that links to a site to purchase parking for the event.
+    /// Available in iOS 18.0.
+    pub fn purchase_parking_url(&mut self, purchase_parking_url: &str) {
+        self.purchase_parking_url = Some(purchase_parking_url.into());
+    }
+
     /// Date and time when the pass becomes relevant. For example, the start time of a movie.
     /// Recommended for event tickets and boarding passes.
     pub fn relevant_date(&mut self, relevant_date: &str) {
         self.relevant_date = Some(relevant_date.into());
     }
 
+    /// Dates and date intervals when the pass is relevant. Replaces relevantDate on devices that
+    /// support it.
+    /// Available in iOS 18.0.
+    pub fn add_relevant_date(&mut self, relevant_date: RelevantDate) {
+        let mut vec = match &self.relevant_dates {
+            Some(vec) => vec.clone(),
+            None => Vec::new(),
+        };
+
+        vec.push(relevant_date);
+        self.relevant_dates = Some(vec);
+    }
+
+    /// Dates and date intervals when the pass is relevant. Replaces relevantDate on devices that
+    /// support it.
+    /// Available in iOS 18.0.
+    pub fn clear_relevant_dates(&mut self) {
+        self.relevant_dates = None;
+    }
+
+    /// A URL that links to a site to sell the ticket of the event.
+    /// Available in iOS 18.0.
+    pub fn sell_url(&mut self, sell_url: &str) {
+        self.sell_url = Some(sell_url.into());
+    }
+
+    /// Machine-readable metadata about the pass as a whole, which the system uses to offer the pass
+    /// and related information to the user. Poster event tickets display the event details from
+    /// these semantics.
+    /// Available in iOS 12.0.
+    pub fn semantics(&mut self, semantics: Semantics) {
+        self.semantics = Some(semantics);
+    }
+
     /// Serial number that uniquely identifies the pass. No two passes with the same pass type
     /// identifier may have the same serial number.
     pub fn serial_number(&mut self, serial_number: &str) {
@@ -443,8 +794,15 @@ impl Template {
     }
 
     /// Information specific to a store card.
+    /// Removes all other pass styles, like `set_style`.
     pub fn store_card(&mut self, store_card: Details) {
-        self.store_card = Some(store_card);
+        self.set_style(PassStyle::StoreCard(store_card));
+    }
+
+    /// If true, the darkened gradient above the header of a poster event ticket is not displayed.
+    /// Available in iOS 18.0.
+    pub fn suppress_header_darkening(&mut self, suppress_header_darkening: bool) {
+        self.suppress_header_darkening = Some(suppress_header_darkening);
     }
 
     /// If true, the strip image is displayed without a shine effect. The default value prior to
@@ -459,6 +817,25 @@ impl Template {
         self.team_identifier = Some(team_identifier.into());
     }
 
+    /// A URL that links to a site to transfer the ticket of the event.
+    /// Available in iOS 18.0.
+    pub fn transfer_url(&mut self, transfer_url: &str) {
+        self.transfer_url = Some(transfer_url.into());
+    }
+
+    /// A URL that links to information about transit options in the area of the event.
+    /// Available in iOS 18.0.
+    pub fn transit_information_url(&mut self, transit_information_url: &str) {
+        self.transit_information_url = Some(transit_information_url.into());
+    }
+
+    /// If true, Wallet computes the foreground and label colors of a poster event ticket from its
+    /// background image, the foreground color and label color of the pass are ignored.
+    /// Available in iOS 18.0.
+    pub fn use_automatic_colors(&mut self, use_automatic_colors: bool) {
+        self.use_automatic_colors = Some(use_automatic_colors);
+    }
+
     /// Custom information for companion apps. This data is not displayed to the user.
     /// For example, a pass for a cafe could include information about the user’s favorite drink
     /// and sandwich in a machine-readable form for the companion app to read, making it easy to
@@ -467,7 +844,7 @@ impl Template {
     pub fn add_user_info(&mut self, key: &str, value: serde_json::Value) {
         let mut map = match &self.user_info {
             Some(map) => map.clone(),
-            None => HashMap::new(),
+            None => BTreeMap::new(),
         };
 
         map.insert(key.into(), Some(value));
@@ -497,6 +874,49 @@ impl Template {
     pub fn web_service_url(&mut self, web_service_url: &str) {
         self.web_service_url = Some(web_service_url.into());
     }
+
+    /// Contrast ratio between the foreground color and the background color, if both are set to
+    /// RGB colors. Text should have a contrast ratio of at least 4.5 to its background.
+    pub fn contrast_ratio(&self) -> Option<f64> {
+        match (&self.foreground_color, &self.background_color) {
+            (Some(foreground_color), Some(background_color)) => {
+                foreground_color.contrast_ratio(background_color)
+            }
+            _ => None,
+        }
+    }
+
+    /// Add a top-level key of the pass.json that is not known to this crate
+    ///
+    /// Keys known to this crate have to be set with their field instead and are rejected with
+    /// `Error::KnownKey`.
+    pub fn add_extra(&mut self, key: &str, value: serde_json::Value) -> Result<()> {
+        if extra::KNOWN_KEYS.contains(&key) {
+            return Err(Error::KnownKey(key.into()));
+        }
+
+        self.extra.insert(key.into(), value);
+        Ok(())
+    }
+
+    /// Json paths of all keys of the pass.json that are not known to this crate, including
+    /// keys of nested objects like `eventTicket.primaryFields[0].futureKey`
+    pub fn unknown_keys(&self) -> Vec<String> {
+        let mut keys = Vec::new();
+        self.extra_keys("", &mut keys);
+
+        keys
+    }
+
+    /// Strict mode: reject a pass.json with keys that are not known to this crate
+    pub fn deny_unknown_keys(&self) -> Result<()> {
+        let keys = self.unknown_keys();
+        if !keys.is_empty() {
+            return Err(Error::UnknownKeys(keys));
+        }
+
+        Ok(())
+    }
 }
 
 /// Information specific to the pass’s barcode.
@@ -525,6 +945,10 @@ pub struct Barcode {
     /// use another encoding that is supported by your barcode scanning infrastructure.
     #[serde(rename = "messageEncoding")]
     pub message_encoding: String,
+
+    /// Keys that are not known to this crate, see `Template::extra`
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl Barcode {
@@ -535,6 +959,7 @@ impl Barcode {
             format,
             message: message.into(),
             message_encoding: message_encoding.into(),
+            extra: serde_json::Map::new(),
         }
     }
 }
@@ -560,6 +985,10 @@ pub struct Beacon {
     #[serde(rename = "relevantText")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub relevant_text: Option<String>,
+
+    /// Keys that are not known to this crate, see `Template::extra`
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl Beacon {
@@ -570,6 +999,7 @@ impl Beacon {
             minor: None,
             proximity_uuid: proximity_uuid.into(),
             relevant_text: None,
+            extra: serde_json::Map::new(),
         }
     }
 }
@@ -611,6 +1041,10 @@ pub struct BoardingPass {
     #[serde(rename = "secondaryFields")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub secondary_fields: Option<Vec<Field>>,
+
+    /// Keys that are not known to this crate, see `Template::extra`
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl BoardingPass {
@@ -623,8 +1057,20 @@ impl BoardingPass {
             header_fields: None,
             primary_fields: None,
             secondary_fields: None,
+            extra: serde_json::Map::new(),
         }
     }
+
+    /// All field sections with their json key
+    pub(crate) fn field_groups(&self) -> Vec<(&'static str, &Option<Vec<Field>>)> {
+        vec![
+            ("headerFields", &self.header_fields),
+            ("primaryFields", &self.primary_fields),
+            ("secondaryFields", &self.secondary_fields),
+            ("auxiliaryFields", &self.auxiliary_fields),
+            ("backFields", &self.back_fields),
+        ]
+    }
 }
 
 /// Keys that define an individual field.
@@ -722,6 +1168,10 @@ pub struct Field {
     #[serde(rename = "row")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub row: Option<i32>,
+
+    /// Keys that are not known to this crate, see `Template::extra`
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl Field {
@@ -743,6 +1193,7 @@ impl Field {
             time_style: None,
             value: ValueUnion::String(value.into()),
             row: None,
+            extra: serde_json::Map::new(),
         }
     }
 
@@ -764,6 +1215,7 @@ impl Field {
             time_style: None,
             value: ValueUnion::Double(value),
             row: None,
+            extra: serde_json::Map::new(),
         }
     }
 
@@ -869,16 +1321,41 @@ impl Field {
 /// intelligently.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Semantics {
+    /// Additional ticket attributes that other semantics of the pass do not include.
+    #[serde(rename = "additionalTicketAttributes")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub additional_ticket_attributes: Option<String>,
+
+    /// The level of admission the ticket provides, such as general admission or VIP.
+    #[serde(rename = "admissionLevel")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub admission_level: Option<String>,
+
+    /// An abbreviation of the level of admission the ticket provides.
+    #[serde(rename = "admissionLevelAbbreviation")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub admission_level_abbreviation: Option<String>,
+
     /// The IATA airline code, such as 'EX' for flightCode 'EX123'.
     #[serde(rename = "airlineCode")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub airline_code: Option<String>,
 
+    /// The Apple Music IDs of the albums of the event, in decreasing order of significance.
+    #[serde(rename = "albumIDs")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub album_ids: Option<Vec<String>>,
+
     /// The Adam IDs for the artists performing, in decreasing order of significance.
     #[serde(rename = "artistIDs")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub artist_i_ds: Option<Vec<String>>,
 
+    /// The name of the person the ticket grants admission to.
+    #[serde(rename = "attendeeName")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attendee_name: Option<String>,
+
     /// The unique abbreviation of the away team's name.
     #[serde(rename = "awayTeamAbbreviation")]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1025,11 +1502,21 @@ pub struct Semantics {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duration: Option<f64>,
 
+    /// A description of the entrance to use to gain access to the ticketed event.
+    #[serde(rename = "entranceDescription")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entrance_description: Option<String>,
+
     /// The date and time the event ends.
     #[serde(rename = "eventEndDate")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub event_end_date: Option<String>,
 
+    /// A message displayed while the event is in progress.
+    #[serde(rename = "eventLiveMessage")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_live_message: Option<String>,
+
     /// The full name for the event, such as the title of a movie.
     #[serde(rename = "eventName")]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1040,6 +1527,11 @@ pub struct Semantics {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub event_start_date: Option<String>,
 
+    /// Details about the start date of the event, e.g. if the start time is not announced yet.
+    #[serde(rename = "eventStartDateInfo")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_start_date_info: Option<EventDateInfo>,
+
     /// The event type.
     #[serde(rename = "eventType")]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1120,6 +1612,11 @@ pub struct Semantics {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub performer_names: Option<Vec<String>>,
 
+    /// The Apple Music IDs of the playlists of the event, in decreasing order of significance.
+    #[serde(rename = "playlistIDs")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub playlist_ids: Option<Vec<String>>,
+
     /// he priority status held by the ticketed passenger
     #[serde(rename = "priorityStatus")]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1147,6 +1644,11 @@ pub struct Semantics {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sport_name: Option<String>,
 
+    /// Whether tailgating is allowed at the event.
+    #[serde(rename = "tailgatingAllowed")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tailgating_allowed: Option<bool>,
+
     /// The total price for the pass.
     #[serde(rename = "totalPrice")]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1186,11 +1688,51 @@ pub struct Semantics {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub vehicle_type: Option<String>,
 
+    /// The date and time the box office of the venue opens.
+    #[serde(rename = "venueBoxOfficeOpenDate")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub venue_box_office_open_date: Option<String>,
+
+    /// The date and time the venue closes.
+    #[serde(rename = "venueCloseDate")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub venue_close_date: Option<String>,
+
+    /// The date and time the doors of the venue open.
+    #[serde(rename = "venueDoorsOpenDate")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub venue_doors_open_date: Option<String>,
+
     /// The full name of the entrance to use to gain access to the ticketed event.
     #[serde(rename = "venueEntrance")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub venue_entrance: Option<String>,
 
+    /// The door of the venue to use to gain access to the ticketed event.
+    #[serde(rename = "venueEntranceDoor")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub venue_entrance_door: Option<String>,
+
+    /// The gate of the venue to use to gain access to the ticketed event.
+    #[serde(rename = "venueEntranceGate")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub venue_entrance_gate: Option<String>,
+
+    /// The portal of the venue to use to gain access to the ticketed event.
+    #[serde(rename = "venueEntrancePortal")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub venue_entrance_portal: Option<String>,
+
+    /// The date and time the fan zone of the venue opens.
+    #[serde(rename = "venueFanZoneOpenDate")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub venue_fan_zone_open_date: Option<String>,
+
+    /// The date and time the gates of the venue open.
+    #[serde(rename = "venueGatesOpenDate")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub venue_gates_open_date: Option<String>,
+
     /// The geographic coordinates of the venue.
     #[serde(rename = "venueLocation")]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1201,23 +1743,52 @@ pub struct Semantics {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub venue_name: Option<String>,
 
+    /// The date and time the venue opens.
+    #[serde(rename = "venueOpenDate")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub venue_open_date: Option<String>,
+
+    /// The date and time the parking lots of the venue open.
+    #[serde(rename = "venueParkingLotsOpenDate")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub venue_parking_lots_open_date: Option<String>,
+
     /// The phone number for enquiries about the venue's ticketed event.
     #[serde(rename = "venuePhoneNumber")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub venue_phone_number: Option<String>,
 
+    /// The Apple Maps place ID of the venue.
+    #[serde(rename = "venuePlaceID")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub venue_place_id: Option<String>,
+
+    /// The name of the city or region of the venue.
+    #[serde(rename = "venueRegionName")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub venue_region_name: Option<String>,
+
     /// The full name of the room where the ticketed event is taking place.
     #[serde(rename = "venueRoom")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub venue_room: Option<String>,
+
+    /// Keys that are not known to this crate, see `Template::extra`
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl Semantics {
     /// Create a new Instance
     pub fn new() -> Self {
         Self {
+            additional_ticket_attributes: None,
+            admission_level: None,
+            admission_level_abbreviation: None,
             airline_code: None,
+            album_ids: None,
             artist_i_ds: None,
+            attendee_name: None,
             away_team_abbreviation: None,
             away_team_location: None,
             away_team_name: None,
@@ -1246,9 +1817,12 @@ impl Semantics {
             destination_station_name: None,
             destination_terminal: None,
             duration: None,
+            entrance_description: None,
             event_end_date: None,
+            event_live_message: None,
             event_name: None,
             event_start_date: None,
+            event_start_date_info: None,
             event_type: None,
             flight_code: None,
             flight_number: None,
@@ -1265,11 +1839,13 @@ impl Semantics {
             original_departure_date: None,
             passenger_name: None,
             performer_names: None,
+            playlist_ids: None,
             priority_status: None,
             seats: None,
             security_screening: None,
             silence_requested: None,
             sport_name: None,
+            tailgating_allowed: None,
             total_price: None,
             transit_provider: None,
             transit_status: None,
@@ -1277,11 +1853,24 @@ impl Semantics {
             vehicle_name: None,
             vehicle_number: None,
             vehicle_type: None,
+            venue_box_office_open_date: None,
+            venue_close_date: None,
+            venue_doors_open_date: None,
             venue_entrance: None,
+            venue_entrance_door: None,
+            venue_entrance_gate: None,
+            venue_entrance_portal: None,
+            venue_fan_zone_open_date: None,
+            venue_gates_open_date: None,
             venue_location: None,
             venue_name: None,
+            venue_open_date: None,
+            venue_parking_lots_open_date: None,
             venue_phone_number: None,
+            venue_place_id: None,
+            venue_region_name: None,
             venue_room: None,
+            extra: serde_json::Map::new(),
         }
     }
 }
@@ -1308,6 +1897,10 @@ pub struct CurrencyAmount {
     #[serde(rename = "currencyCode")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub currency_code: Option<String>,
+
+    /// Keys that are not known to this crate, see `Template::extra`
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl CurrencyAmount {
@@ -1316,6 +1909,7 @@ impl CurrencyAmount {
         Self {
             amount: None,
             currency_code: None,
+            extra: serde_json::Map::new(),
         }
     }
 }
@@ -1356,6 +1950,10 @@ pub struct Location {
     #[serde(rename = "relevantText")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub relevant_text: Option<String>,
+
+    /// Keys that are not known to this crate, see `Template::extra`
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl Location {
@@ -1366,6 +1964,53 @@ impl Location {
             latitude,
             longitude,
             relevant_text: None,
+            extra: serde_json::Map::new(),
+        }
+    }
+}
+
+/// A date or date interval when the pass is relevant.
+/// Available in iOS 18.0.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelevantDate {
+    /// The date and time when the pass is relevant, used instead of a date interval.
+    #[serde(rename = "date")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+
+    /// The date and time when the pass stops being relevant.
+    #[serde(rename = "endDate")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+
+    /// The date and time when the pass becomes relevant.
+    #[serde(rename = "startDate")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+
+    /// Keys that are not known to this crate, see `Template::extra`
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl RelevantDate {
+    /// Create a new Instance with a single date
+    pub fn new_date(date: &str) -> Self {
+        Self {
+            date: Some(date.into()),
+            end_date: None,
+            start_date: None,
+            extra: serde_json::Map::new(),
+        }
+    }
+
+    /// Create a new Instance with a date interval
+    pub fn new_interval(start_date: &str, end_date: &str) -> Self {
+        Self {
+            date: None,
+            end_date: Some(end_date.into()),
+            start_date: Some(start_date.into()),
+            extra: serde_json::Map::new(),
         }
     }
 }
@@ -1411,6 +2056,10 @@ pub struct PersonNameComponents {
     #[serde(rename = "phoneticRepresentation")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub phonetic_representation: Box<Option<PhoneticRepresentation>>,
+
+    /// Keys that are not known to this crate, see `Template::extra`
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl PersonNameComponents {
@@ -1424,6 +2073,7 @@ impl PersonNameComponents {
             name_suffix: None,
             nickname: None,
             phonetic_representation: Box::new(None),
+            extra: serde_json::Map::new(),
         }
     }
 }
@@ -1477,6 +2127,10 @@ pub struct PhoneticRepresentation {
     #[serde(rename = "phoneticRepresentation")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub phonetic_representation: Box<Option<PhoneticRepresentation>>,
+
+    /// Keys that are not known to this crate, see `Template::extra`
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl PhoneticRepresentation {
@@ -1490,6 +2144,7 @@ impl PhoneticRepresentation {
             name_suffix: None,
             nickname: None,
             phonetic_representation: Box::new(None),
+            extra: serde_json::Map::new(),
         }
     }
 }
@@ -1503,6 +2158,11 @@ impl Default for PhoneticRepresentation {
 /// A dictionary with seat information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Seat {
+    /// Seat aisle
+    #[serde(rename = "seatAisle")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seat_aisle: Option<String>,
+
     /// Seat description
     #[serde(rename = "seatDescription")]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1513,6 +2173,11 @@ pub struct Seat {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub seat_identifier: Option<String>,
 
+    /// Seat level
+    #[serde(rename = "seatLevel")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seat_level: Option<String>,
+
     /// Seat number
     #[serde(rename = "seatNumber")]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1528,22 +2193,35 @@ pub struct Seat {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub seat_section: Option<String>,
 
+    /// Seat section color, specified as a CSS-style RGB triple
+    #[serde(rename = "seatSectionColor")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seat_section_color: Option<Color>,
+
     /// Seat type
     #[serde(rename = "seatType")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub seat_type: Option<String>,
+
+    /// Keys that are not known to this crate, see `Template::extra`
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl Seat {
     /// Create a new Instance
     pub fn new() -> Self {
         Self {
+            seat_aisle: None,
             seat_description: None,
             seat_identifier: None,
+            seat_level: None,
             seat_number: None,
             seat_row: None,
             seat_section: None,
+            seat_section_color: None,
             seat_type: None,
+            extra: serde_json::Map::new(),
         }
     }
 }
@@ -1554,6 +2232,53 @@ impl Default for Seat {
     }
 }
 
+/// Details about the date of an event.
+/// Available in iOS 18.0.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventDateInfo {
+    /// The date and time of the event.
+    #[serde(rename = "date")]
+    pub date: String,
+
+    /// If true, only the date of the event is displayed.
+    #[serde(rename = "ignoreTimeComponents")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ignore_time_components: Option<bool>,
+
+    /// The time zone the date is displayed in, such as America/Los_Angeles.
+    #[serde(rename = "timeZone")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_zone: Option<String>,
+
+    /// If true, the date of the event is displayed as not announced yet.
+    #[serde(rename = "unannounced")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unannounced: Option<bool>,
+
+    /// If true, the date of the event is displayed as not determined yet.
+    #[serde(rename = "undetermined")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub undetermined: Option<bool>,
+
+    /// Keys that are not known to this crate, see `Template::extra`
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl EventDateInfo {
+    /// Create a new Instance
+    pub fn new(date: &str) -> Self {
+        Self {
+            date: date.into(),
+            ignore_time_components: None,
+            time_zone: None,
+            unannounced: None,
+            undetermined: None,
+            extra: serde_json::Map::new(),
+        }
+    }
+}
+
 /// Information specific to a coupon.
 ///
 /// Information specific to an event ticket.
@@ -1569,6 +2294,12 @@ impl Default for Seat {
 /// of the pass.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Details {
+    /// Fields to be displayed in the additional information section of a poster event ticket.
+    /// Available in iOS 18.0.
+    #[serde(rename = "additionalInfoFields")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub additional_info_fields: Option<Vec<Field>>,
+
     /// Additional fields to be displayed on the front of the pass.
     #[serde(rename = "auxiliaryFields")]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1600,26 +2331,63 @@ pub struct Details {
     #[serde(rename = "transitType")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub transit_type: Option<TransitType>,
+
+    /// Keys that are not known to this crate, see `Template::extra`
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl Details {
     /// Create a new Instance
     pub fn new() -> Self {
         Self {
+            additional_info_fields: None,
             auxiliary_fields: None,
             back_fields: None,
             header_fields: None,
             primary_fields: None,
             secondary_fields: None,
             transit_type: None,
+            extra: serde_json::Map::new(),
         }
     }
 
+    /// All field sections with their json key
+    pub(crate) fn field_groups(&self) -> Vec<(&'static str, &Option<Vec<Field>>)> {
+        vec![
+            ("headerFields", &self.header_fields),
+            ("primaryFields", &self.primary_fields),
+            ("secondaryFields", &self.secondary_fields),
+            ("auxiliaryFields", &self.auxiliary_fields),
+            ("additionalInfoFields", &self.additional_info_fields),
+            ("backFields", &self.back_fields),
+        ]
+    }
+
     /// Type of transit.
     pub fn transit_type(&mut self, transit_type: TransitType) {
         self.transit_type = Some(transit_type);
     }
 
+    /// Add field to be displayed in the additional information section of a poster event ticket.
+    /// Available in iOS 18.0.
+    pub fn add_additional_info_field(&mut self, field: Field) {
+        let mut vec = match &self.additional_info_fields {
+            Some(vec) => vec.clone(),
+            None => Vec::new(),
+        };
+        vec.push(field);
+
+        self.additional_info_fields = Some(vec);
+    }
+
+    /// Remove fields to be displayed in the additional information section of a poster event
+    /// ticket.
+    /// Available in iOS 18.0.
+    pub fn clear_additional_info_fields(&mut self) {
+        self.additional_info_fields = None;
+    }
+
     /// Add additional field to be displayed on the front of the pass.
     pub fn add_auxiliary_field(&mut self, field: Field) {
         let mut vec = match &self.auxiliary_fields {
@@ -1727,6 +2495,10 @@ pub struct Nfc {
     /// Messages longer than 64 bytes are truncated by the system.
     #[serde(rename = "message")]
     pub message: String,
+
+    /// Keys that are not known to this crate, see `Template::extra`
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl Nfc {
@@ -1735,6 +2507,7 @@ impl Nfc {
         Self {
             encryption_public_key: None,
             message: message.into(),
+            extra: serde_json::Map::new(),
         }
     }
 }
@@ -1855,6 +2628,24 @@ pub enum EventType {
     PkEventTypeWorkshop,
 }
 
+/// Style a pass can be displayed in.
+/// Available in iOS 18.0.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum StyleScheme {
+    /// Style scheme `eventTicket`
+    #[serde(rename = "eventTicket")]
+    EventTicket,
+
+    /// Style scheme `posterEventTicket`
+    #[serde(rename = "posterEventTicket")]
+    PosterEventTicket,
+
+    /// Style scheme that is not known to this crate, e.g. added by a newer iOS version. It is
+    /// kept as it is and written back when the pass is exported.
+    #[serde(untagged)]
+    Other(String),
+}
+
 /// Alignment for the field’s contents.
 /// This key is not allowed for primary fields or back fields.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -0,0 +1,159 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Error, Result};
+
+/// Color of a pass, serialized as CSS-style RGB triple, such as `rgb(23, 187, 82)`
+///
+/// Colors can be created from RGB tuples and parsed from RGB triples and hex values like
+/// `#17bb52`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Color {
+    /// Color given by its red, green and blue channel
+    Rgb {
+        /// Red channel
+        red: u8,
+        /// Green channel
+        green: u8,
+        /// Blue channel
+        blue: u8,
+    },
+
+    /// Color of a pass.json that is not a CSS-style RGB triple, e.g. `rgba(1, 2, 3, 0.5)`. It is
+    /// kept as it is and written back when the pass is exported, `Template::validate` warns
+    /// about it.
+    Other(String),
+}
+
+impl Color {
+    /// Create a new Instance
+    pub fn new(red: u8, green: u8, blue: u8) -> Self {
+        Color::Rgb { red, green, blue }
+    }
+
+    /// Parse a hex color like `#17bb52` or the short form `#1b5`, the leading `#` is optional
+    pub fn from_hex(value: &str) -> Result<Self> {
+        let hex = value.trim().trim_start_matches('#');
+        let invalid = || Error::InvalidColor(value.into());
+
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+
+        let channel = |digits: &str| u8::from_str_radix(digits, 16).map_err(|_| invalid());
+        match hex.len() {
+            6 => Ok(Self::new(
+                channel(&hex[0..2])?,
+                channel(&hex[2..4])?,
+                channel(&hex[4..6])?,
+            )),
+            3 => Ok(Self::new(
+                channel(&hex[0..1])? * 17,
+                channel(&hex[1..2])? * 17,
+                channel(&hex[2..3])? * 17,
+            )),
+            _ => Err(invalid()),
+        }
+    }
+
+    /// Red, green and blue channel, `None` for `Color::Other`
+    pub fn rgb(&self) -> Option<(u8, u8, u8)> {
+        match self {
+            Color::Rgb { red, green, blue } => Some((*red, *green, *blue)),
+            Color::Other(_) => None,
+        }
+    }
+
+    /// Format the color as hex value like `#17bb52`, `None` for `Color::Other`
+    pub fn to_hex(&self) -> Option<String> {
+        let (red, green, blue) = self.rgb()?;
+
+        Some(format!("#{:02x}{:02x}{:02x}", red, green, blue))
+    }
+
+    /// Relative luminance as defined by WCAG 2, from 0 for black to 1 for white. `None` for
+    /// `Color::Other`.
+    pub fn relative_luminance(&self) -> Option<f64> {
+        let linear = |channel: u8| {
+            let value = f64::from(channel) / 255.0;
+            if value <= 0.03928 {
+                value / 12.92
+            } else {
+                ((value + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        let (red, green, blue) = self.rgb()?;
+
+        Some(0.2126 * linear(red) + 0.7152 * linear(green) + 0.0722 * linear(blue))
+    }
+
+    /// Contrast ratio between two colors as defined by WCAG 2, from 1 for equal colors to 21 for
+    /// black and white. Text should have a contrast ratio of at least 4.5 to its background.
+    /// `None` if one of the colors is `Color::Other`.
+    pub fn contrast_ratio(&self, other: &Color) -> Option<f64> {
+        let a = self.relative_luminance()?;
+        let b = other.relative_luminance()?;
+
+        Some((a.max(b) + 0.05) / (a.min(b) + 0.05))
+    }
+}
+
+impl From<(u8, u8, u8)> for Color {
+    fn from((red, green, blue): (u8, u8, u8)) -> Self {
+        Self::new(red, green, blue)
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Color::Rgb { red, green, blue } => write!(f, "rgb({}, {}, {})", red, green, blue),
+            Color::Other(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl FromStr for Color {
+    type Err = Error;
+
+    /// Parse a CSS-style RGB triple like `rgb(23, 187, 82)` or a hex color like `#17bb52`
+    fn from_str(value: &str) -> Result<Self> {
+        let trimmed = value.trim();
+        if trimmed.starts_with('#') {
+            return Self::from_hex(trimmed);
+        }
+
+        let channels = trimmed
+            .strip_prefix("rgb(")
+            .and_then(|channels| channels.strip_suffix(')'))
+            .map(|channels| {
+                channels
+                    .split(',')
+                    .map(|channel| channel.trim().parse::<u8>())
+                    .collect::<std::result::Result<Vec<_>, _>>()
+            });
+
+        match channels {
+            Some(Ok(channels)) if channels.len() == 3 => {
+                Ok(Self::new(channels[0], channels[1], channels[2]))
+            }
+            _ => Err(Error::InvalidColor(value.into())),
+        }
+    }
+}
+
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+
+        // Keep colors Apple Wallet does not understand, so loading a pass does not fail
+        Ok(value.parse().unwrap_or(Color::Other(value)))
+    }
+}
@@ -0,0 +1,249 @@
+use chrono::{DateTime, FixedOffset, SecondsFormat, TimeZone};
+
+use super::{EStyle, Field, RelevantDate, Semantics, Template, ValueUnion};
+use crate::{Error, Result};
+
+/// Format a date as ISO 8601 date and time with time zone, e.g. `2024-11-01T18:30:00+01:00`, the
+/// format Apple Wallet accepts for all dates of a pass. Fractions of seconds are dropped.
+pub fn format_date<Tz: TimeZone>(date: &DateTime<Tz>) -> String {
+    date.fixed_offset()
+        .to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Parse an ISO 8601 date and time with time zone, with or without seconds
+pub fn parse_date(value: &str) -> Result<DateTime<FixedOffset>> {
+    parse_date_with_key("date", value)
+}
+
+fn parse_date_with_key(key: &str, value: &str) -> Result<DateTime<FixedOffset>> {
+    if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+        return Ok(date);
+    }
+
+    // Apple Wallet also accepts dates without seconds, e.g. `2024-11-01T18:30Z`
+    let offset_value = match value.strip_suffix('Z') {
+        Some(value) => format!("{}+00:00", value),
+        None => value.to_owned(),
+    };
+    DateTime::parse_from_str(&offset_value, "%Y-%m-%dT%H:%M%:z").map_err(|_| Error::InvalidDate {
+        key: key.into(),
+        value: value.into(),
+    })
+}
+
+fn parse_optional_date(key: &str, value: &Option<String>) -> Result<Option<DateTime<FixedOffset>>> {
+    value
+        .as_deref()
+        .map(|value| parse_date_with_key(key, value))
+        .transpose()
+}
+
+impl Template {
+    /// Date and time when the pass becomes relevant. For example, the start time of a movie.
+    /// Recommended for event tickets and boarding passes.
+    pub fn relevant_date_time<Tz: TimeZone>(&mut self, relevant_date: &DateTime<Tz>) {
+        self.relevant_date = Some(format_date(relevant_date));
+    }
+
+    /// Parse the date and time when the pass becomes relevant
+    pub fn parse_relevant_date(&self) -> Result<Option<DateTime<FixedOffset>>> {
+        parse_optional_date("relevantDate", &self.relevant_date)
+    }
+
+    /// Date and time when the pass expires.
+    /// Available in iOS 7.0.
+    pub fn expiration_date_time<Tz: TimeZone>(&mut self, expiration_date: &DateTime<Tz>) {
+        self.expiration_date = Some(format_date(expiration_date));
+    }
+
+    /// Parse the date and time when the pass expires
+    pub fn parse_expiration_date(&self) -> Result<Option<DateTime<FixedOffset>>> {
+        parse_optional_date("expirationDate", &self.expiration_date)
+    }
+
+    /// Validate that all dates of the pass, including semantics and date fields, are ISO 8601
+    /// dates Apple Wallet accepts. The error names the json path of the first invalid date,
+    /// `Template::validate` reports all of them.
+    pub fn validate_dates(&self) -> Result<()> {
+        for (key, value) in self.date_values() {
+            parse_date_with_key(&key, value)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl RelevantDate {
+    /// Create a new Instance with a single typed date
+    pub fn from_date<Tz: TimeZone>(date: &DateTime<Tz>) -> Self {
+        Self::new_date(&format_date(date))
+    }
+
+    /// Create a new Instance with a typed date interval
+    pub fn from_interval<Tz: TimeZone>(start_date: &DateTime<Tz>, end_date: &DateTime<Tz>) -> Self {
+        Self::new_interval(&format_date(start_date), &format_date(end_date))
+    }
+
+    /// Parse the date and time when the pass is relevant
+    pub fn parse_date(&self) -> Result<Option<DateTime<FixedOffset>>> {
+        parse_optional_date("date", &self.date)
+    }
+
+    /// Parse the date and time when the pass becomes relevant
+    pub fn parse_start_date(&self) -> Result<Option<DateTime<FixedOffset>>> {
+        parse_optional_date("startDate", &self.start_date)
+    }
+
+    /// Parse the date and time when the pass stops being relevant
+    pub fn parse_end_date(&self) -> Result<Option<DateTime<FixedOffset>>> {
+        parse_optional_date("endDate", &self.end_date)
+    }
+}
+
+impl Semantics {
+    /// The updated date and time of arrival, if different than the original scheduled date.
+    pub fn current_arrival_date_time<Tz: TimeZone>(&mut self, current_arrival_date: &DateTime<Tz>) {
+        self.current_arrival_date = Some(format_date(current_arrival_date));
+    }
+
+    /// The updated date and time of boarding, if different than the original scheduled date.
+    pub fn current_boarding_date_time<Tz: TimeZone>(
+        &mut self,
+        current_boarding_date: &DateTime<Tz>,
+    ) {
+        self.current_boarding_date = Some(format_date(current_boarding_date));
+    }
+
+    /// The updated date and time of departure, if different than the original scheduled date.
+    pub fn current_departure_date_time<Tz: TimeZone>(
+        &mut self,
+        current_departure_date: &DateTime<Tz>,
+    ) {
+        self.current_departure_date = Some(format_date(current_departure_date));
+    }
+
+    /// The date and time the event ends.
+    pub fn event_end_date_time<Tz: TimeZone>(&mut self, event_end_date: &DateTime<Tz>) {
+        self.event_end_date = Some(format_date(event_end_date));
+    }
+
+    /// The date and time the event starts.
+    pub fn event_start_date_time<Tz: TimeZone>(&mut self, event_start_date: &DateTime<Tz>) {
+        self.event_start_date = Some(format_date(event_start_date));
+    }
+
+    /// The original scheduled date and time of arrival.
+    pub fn original_arrival_date_time<Tz: TimeZone>(
+        &mut self,
+        original_arrival_date: &DateTime<Tz>,
+    ) {
+        self.original_arrival_date = Some(format_date(original_arrival_date));
+    }
+
+    /// The original scheduled date and time of boarding.
+    pub fn original_boarding_date_time<Tz: TimeZone>(
+        &mut self,
+        original_boarding_date: &DateTime<Tz>,
+    ) {
+        self.original_boarding_date = Some(format_date(original_boarding_date));
+    }
+
+    /// The original scheduled date and time of departure.
+    pub fn original_departure_date_time<Tz: TimeZone>(
+        &mut self,
+        original_departure_date: &DateTime<Tz>,
+    ) {
+        self.original_departure_date = Some(format_date(original_departure_date));
+    }
+
+    /// The date and time the box office of the venue opens.
+    pub fn venue_box_office_open_date_time<Tz: TimeZone>(
+        &mut self,
+        venue_box_office_open_date: &DateTime<Tz>,
+    ) {
+        self.venue_box_office_open_date = Some(format_date(venue_box_office_open_date));
+    }
+
+    /// The date and time the venue closes.
+    pub fn venue_close_date_time<Tz: TimeZone>(&mut self, venue_close_date: &DateTime<Tz>) {
+        self.venue_close_date = Some(format_date(venue_close_date));
+    }
+
+    /// The date and time the doors of the venue open.
+    pub fn venue_doors_open_date_time<Tz: TimeZone>(
+        &mut self,
+        venue_doors_open_date: &DateTime<Tz>,
+    ) {
+        self.venue_doors_open_date = Some(format_date(venue_doors_open_date));
+    }
+
+    /// The date and time the fan zone of the venue opens.
+    pub fn venue_fan_zone_open_date_time<Tz: TimeZone>(
+        &mut self,
+        venue_fan_zone_open_date: &DateTime<Tz>,
+    ) {
+        self.venue_fan_zone_open_date = Some(format_date(venue_fan_zone_open_date));
+    }
+
+    /// The date and time the gates of the venue open.
+    pub fn venue_gates_open_date_time<Tz: TimeZone>(
+        &mut self,
+        venue_gates_open_date: &DateTime<Tz>,
+    ) {
+        self.venue_gates_open_date = Some(format_date(venue_gates_open_date));
+    }
+
+    /// The date and time the venue opens.
+    pub fn venue_open_date_time<Tz: TimeZone>(&mut self, venue_open_date: &DateTime<Tz>) {
+        self.venue_open_date = Some(format_date(venue_open_date));
+    }
+
+    /// The date and time the parking lots of the venue open.
+    pub fn venue_parking_lots_open_date_time<Tz: TimeZone>(
+        &mut self,
+        venue_parking_lots_open_date: &DateTime<Tz>,
+    ) {
+        self.venue_parking_lots_open_date = Some(format_date(venue_parking_lots_open_date));
+    }
+
+    /// Parse all dates of the semantics, keyed by their json key
+    pub fn parse_dates(&self) -> Result<Vec<(&'static str, DateTime<FixedOffset>)>> {
+        let mut dates = Vec::new();
+
+        for (key, value) in self.dates() {
+            if let Some(date) = parse_optional_date(key, value)? {
+                dates.push((key, date));
+            }
+        }
+
+        Ok(dates)
+    }
+}
+
+impl Field {
+    /// Create a new Instance with a date value, displayed with medium date style and short time
+    /// style
+    pub fn new_date<Tz: TimeZone>(key: &str, value: &DateTime<Tz>) -> Self {
+        let mut field = Self::new_string(key, &format_date(value));
+        field.date_style(EStyle::PkDateStyleMedium);
+        field.time_style(EStyle::PkDateStyleShort);
+
+        field
+    }
+
+    /// Parse the value of a field with a date or time style. Returns `None` for fields without a
+    /// date or time style.
+    pub fn parse_date_value(&self) -> Result<Option<DateTime<FixedOffset>>> {
+        if self.date_style.is_none() && self.time_style.is_none() {
+            return Ok(None);
+        }
+
+        match &self.value {
+            ValueUnion::String(value) => parse_date_with_key(&self.key, value).map(Some),
+            ValueUnion::Double(value) => Err(Error::InvalidDate {
+                key: self.key.clone(),
+                value: value.to_string(),
+            }),
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use super::{
+    Barcode, Beacon, BoardingPass, CurrencyAmount, Details, EventDateInfo, Field, Location, Nfc,
+    PersonNameComponents, PhoneticRepresentation, RelevantDate, Seat, Semantics, Template,
+};
+
+/// Top-level keys of the pass.json that are known to this crate
+pub(crate) const KNOWN_KEYS: &[&str] = &[
+    "accessibilityURL",
+    "addOnURL",
+    "appLaunchURL",
+    "associatedStoreIdentifiers",
+    "authenticationToken",
+    "auxiliaryStoreIdentifiers",
+    "backgroundColor",
+    "bagPolicyURL",
+    "barcode",
+    "barcodes",
+    "beacons",
+    "boardingPass",
+    "contactVenueEmail",
+    "contactVenuePhoneNumber",
+    "contactVenueWebsite",
+    "coupon",
+    "description",
+    "directionsInformationURL",
+    "eventLogoText",
+    "eventTicket",
+    "expirationDate",
+    "footerBackgroundColor",
+    "foregroundColor",
+    "formatVersion",
+    "generic",
+    "groupingIdentifier",
+    "labelColor",
+    "locations",
+    "logoText",
+    "maxDistance",
+    "merchandiseURL",
+    "nfc",
+    "orderFoodURL",
+    "organizationName",
+    "parkingInformationURL",
+    "passTypeIdentifier",
+    "preferredStyleSchemes",
+    "purchaseParkingURL",
+    "relevantDate",
+    "relevantDates",
+    "sellURL",
+    "semantics",
+    "serialNumber",
+    "storeCard",
+    "suppressHeaderDarkening",
+    "suppressStripShine",
+    "teamIdentifier",
+    "transferURL",
+    "transitInformationURL",
+    "useAutomaticColors",
+    "userInfo",
+    "voided",
+    "webServiceURL",
+];
+
+/// Collects the json paths of the keys that are not known to this crate
+pub(crate) trait ExtraKeys {
+    /// Add the json paths of the unknown keys of this value with the given json path to `keys`
+    fn extra_keys(&self, path: &str, keys: &mut Vec<String>);
+}
+
+impl<T: ExtraKeys> ExtraKeys for Option<T> {
+    fn extra_keys(&self, path: &str, keys: &mut Vec<String>) {
+        if let Some(value) = self {
+            value.extra_keys(path, keys);
+        }
+    }
+}
+
+impl<T: ExtraKeys> ExtraKeys for Box<T> {
+    fn extra_keys(&self, path: &str, keys: &mut Vec<String>) {
+        self.as_ref().extra_keys(path, keys);
+    }
+}
+
+impl<T: ExtraKeys> ExtraKeys for Vec<T> {
+    fn extra_keys(&self, path: &str, keys: &mut Vec<String>) {
+        for (index, value) in self.iter().enumerate() {
+            value.extra_keys(&format!("{}[{}]", path, index), keys);
+        }
+    }
+}
+
+/// Json path of a key of the object with the given json path
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.into()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+/// Implement `ExtraKeys` for a struct with an `extra` map and the given nested values, each
+/// with its json key
+macro_rules! impl_extra_keys {
+    ($type:ty { $($key:literal => $field:ident),* $(,)? }) => {
+        impl ExtraKeys for $type {
+            fn extra_keys(&self, path: &str, keys: &mut Vec<String>) {
+                keys.extend(self.extra.keys().map(|key| join(path, key)));
+                $(self.$field.extra_keys(&join(path, $key), keys);)*
+            }
+        }
+    };
+}
+
+impl_extra_keys!(Template {
+    "barcode" => barcode,
+    "barcodes" => barcodes,
+    "beacons" => beacons,
+    "boardingPass" => boarding_pass,
+    "coupon" => coupon,
+    "eventTicket" => event_ticket,
+    "generic" => generic,
+    "locations" => locations,
+    "nfc" => nfc,
+    "relevantDates" => relevant_dates,
+    "semantics" => semantics,
+    "storeCard" => store_card,
+});
+
+impl_extra_keys!(BoardingPass {
+    "auxiliaryFields" => auxiliary_fields,
+    "backFields" => back_fields,
+    "headerFields" => header_fields,
+    "primaryFields" => primary_fields,
+    "secondaryFields" => secondary_fields,
+});
+
+impl_extra_keys!(Details {
+    "additionalInfoFields" => additional_info_fields,
+    "auxiliaryFields" => auxiliary_fields,
+    "backFields" => back_fields,
+    "headerFields" => header_fields,
+    "primaryFields" => primary_fields,
+    "secondaryFields" => secondary_fields,
+});
+
+impl_extra_keys!(Field { "semantics" => semantics });
+
+impl_extra_keys!(Semantics {
+    "balance" => balance,
+    "departureLocation" => departure_location,
+    "destinationLocation" => destination_location,
+    "destinationLocationDescription" => destination_location_description,
+    "eventStartDateInfo" => event_start_date_info,
+    "passengerName" => passenger_name,
+    "seats" => seats,
+    "totalPrice" => total_price,
+    "venueLocation" => venue_location,
+});
+
+impl_extra_keys!(PersonNameComponents {
+    "phoneticRepresentation" => phonetic_representation,
+});
+
+impl_extra_keys!(PhoneticRepresentation {
+    "phoneticRepresentation" => phonetic_representation,
+});
+
+impl_extra_keys!(Barcode {});
+impl_extra_keys!(Beacon {});
+impl_extra_keys!(CurrencyAmount {});
+impl_extra_keys!(EventDateInfo {});
+impl_extra_keys!(Location {});
+impl_extra_keys!(Nfc {});
+impl_extra_keys!(RelevantDate {});
+impl_extra_keys!(Seat {});
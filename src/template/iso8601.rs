@@ -0,0 +1,202 @@
+use super::{Field, RelevantDate, Semantics, Template, ValueUnion};
+
+/// Check if the value is an ISO 8601 date and time with time zone Apple Wallet accepts, like
+/// `2024-11-01T18:30:00+01:00` or `2024-11-01T18:30Z`
+pub(crate) fn is_date(value: &str) -> bool {
+    parse(value).is_some()
+}
+
+/// Parse the date, time and time zone, `None` if the value is not a valid date
+fn parse(value: &str) -> Option<()> {
+    let (date, time) = value.split_once('T')?;
+
+    let (year, rest) = number(date, 4)?;
+    let (month, rest) = number(rest.strip_prefix('-')?, 2)?;
+    let (day, rest) = number(rest.strip_prefix('-')?, 2)?;
+    if !rest.is_empty() || !(1..=12).contains(&month) || day == 0 || day > days(year, month) {
+        return None;
+    }
+
+    let (hour, rest) = number(time, 2)?;
+    let (minute, mut rest) = number(rest.strip_prefix(':')?, 2)?;
+    if hour > 23 || minute > 59 {
+        return None;
+    }
+
+    // Seconds and fractions of seconds are optional, a leap second may be 60
+    if let Some(seconds) = rest.strip_prefix(':') {
+        let (second, seconds) = number(seconds, 2)?;
+        if second > 60 {
+            return None;
+        }
+        rest = match seconds.strip_prefix('.') {
+            Some(fraction) => {
+                let digits = fraction.bytes().take_while(u8::is_ascii_digit).count();
+                if digits == 0 {
+                    return None;
+                }
+                &fraction[digits..]
+            }
+            None => seconds,
+        };
+    }
+
+    if rest == "Z" {
+        return Some(());
+    }
+    let offset = rest.strip_prefix('+').or_else(|| rest.strip_prefix('-'))?;
+    let (offset_hour, offset) = number(offset, 2)?;
+    let (offset_minute, offset) = number(offset.strip_prefix(':')?, 2)?;
+    if !offset.is_empty() || offset_hour > 23 || offset_minute > 59 {
+        return None;
+    }
+
+    Some(())
+}
+
+/// Split a number with exactly `len` digits off the start of the value
+fn number(value: &str, len: usize) -> Option<(u32, &str)> {
+    let digits = value.get(..len)?;
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    Some((digits.parse().ok()?, &value[len..]))
+}
+
+/// Number of days of a month of the gregorian calendar
+fn days(year: u32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Check if the year of the gregorian calendar has a 29th of February
+fn is_leap_year(year: u32) -> bool {
+    year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+}
+
+impl Template {
+    /// All dates of the pass with their json path, including relevant dates, semantics and the
+    /// values of fields with a date or time style
+    pub(crate) fn date_values(&self) -> Vec<(String, &str)> {
+        let mut dates = Vec::new();
+
+        let keys = [
+            ("expirationDate", &self.expiration_date),
+            ("relevantDate", &self.relevant_date),
+        ];
+        for (key, value) in keys {
+            if let Some(value) = value {
+                dates.push((key.to_owned(), value.as_str()));
+            }
+        }
+
+        for (index, relevant_date) in self.relevant_dates.iter().flatten().enumerate() {
+            relevant_date.date_values(&format!("relevantDates[{}].", index), &mut dates);
+        }
+
+        if let Some(semantics) = &self.semantics {
+            semantics.date_values("semantics.", &mut dates);
+        }
+
+        let mut groups = Vec::new();
+        if let Some(boarding_pass) = &self.boarding_pass {
+            groups.push(("boardingPass", boarding_pass.field_groups()));
+        }
+        let styles = [
+            ("coupon", &self.coupon),
+            ("eventTicket", &self.event_ticket),
+            ("generic", &self.generic),
+            ("storeCard", &self.store_card),
+        ];
+        for (key, details) in styles {
+            if let Some(details) = details {
+                groups.push((key, details.field_groups()));
+            }
+        }
+        for (style, fields) in groups {
+            for (key, fields) in fields {
+                for (index, field) in fields.iter().flatten().enumerate() {
+                    field.date_values(&format!("{}.{}[{}].", style, key, index), &mut dates);
+                }
+            }
+        }
+
+        dates
+    }
+}
+
+impl RelevantDate {
+    fn date_values<'a>(&'a self, path: &str, dates: &mut Vec<(String, &'a str)>) {
+        let keys = [
+            ("date", &self.date),
+            ("endDate", &self.end_date),
+            ("startDate", &self.start_date),
+        ];
+        for (key, value) in keys {
+            if let Some(value) = value {
+                dates.push((format!("{}{}", path, key), value.as_str()));
+            }
+        }
+    }
+}
+
+impl Semantics {
+    /// All dates of the semantics with their json key
+    pub fn dates(&self) -> Vec<(&'static str, &Option<String>)> {
+        vec![
+            ("currentArrivalDate", &self.current_arrival_date),
+            ("currentBoardingDate", &self.current_boarding_date),
+            ("currentDepartureDate", &self.current_departure_date),
+            ("eventEndDate", &self.event_end_date),
+            ("eventStartDate", &self.event_start_date),
+            ("originalArrivalDate", &self.original_arrival_date),
+            ("originalBoardingDate", &self.original_boarding_date),
+            ("originalDepartureDate", &self.original_departure_date),
+            ("venueBoxOfficeOpenDate", &self.venue_box_office_open_date),
+            ("venueCloseDate", &self.venue_close_date),
+            ("venueDoorsOpenDate", &self.venue_doors_open_date),
+            ("venueFanZoneOpenDate", &self.venue_fan_zone_open_date),
+            ("venueGatesOpenDate", &self.venue_gates_open_date),
+            ("venueOpenDate", &self.venue_open_date),
+            (
+                "venueParkingLotsOpenDate",
+                &self.venue_parking_lots_open_date,
+            ),
+        ]
+    }
+
+    fn date_values<'a>(&'a self, path: &str, dates: &mut Vec<(String, &'a str)>) {
+        for (key, value) in self.dates() {
+            if let Some(value) = value {
+                dates.push((format!("{}{}", path, key), value.as_str()));
+            }
+        }
+
+        if let Some(event_start_date_info) = &self.event_start_date_info {
+            dates.push((
+                format!("{}eventStartDateInfo.date", path),
+                event_start_date_info.date.as_str(),
+            ));
+        }
+    }
+}
+
+impl Field {
+    fn date_values<'a>(&'a self, path: &str, dates: &mut Vec<(String, &'a str)>) {
+        // Numbers with a date or time style are reported by `Template::validate`
+        if self.date_style.is_some() || self.time_style.is_some() {
+            if let ValueUnion::String(value) = &self.value {
+                dates.push((format!("{}value", path), value.as_str()));
+            }
+        }
+
+        if let Some(semantics) = &self.semantics {
+            semantics.date_values(&format!("{}semantics.", path), dates);
+        }
+    }
+}
@@ -0,0 +1,289 @@
+use crate::asset::{ImageAsset, ImageKind};
+
+use super::{BarcodeFormat, Diagnostic, Field, PassStyle, Template, ValueUnion};
+
+/// Images of a pass that change the layout of its fields
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LayoutImages {
+    /// The pass contains a `background.png`
+    pub background: bool,
+    /// The pass contains a `strip.png`
+    pub strip: bool,
+    /// The pass contains a `thumbnail.png`
+    pub thumbnail: bool,
+}
+
+impl LayoutImages {
+    /// Create a new Instance without images
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Detect the images from the asset names of a pass, including scaled and localized variants
+    /// like `strip@2x.png` or `en.lproj/thumbnail.png`
+    pub fn from_asset_names<I, S>(names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut images = Self::new();
+
+        for name in names {
+            match ImageAsset::from_name(name.as_ref()).map(|image| image.kind) {
+                Some(ImageKind::Background) => images.background = true,
+                Some(ImageKind::Strip) => images.strip = true,
+                Some(ImageKind::Thumbnail) => images.thumbnail = true,
+                _ => {}
+            }
+        }
+
+        images
+    }
+}
+
+/// Number of fields Apple Wallet displays per section of a pass style, and the number of
+/// characters that fit into a section before values are truncated
+///
+/// The numbers of characters are estimates for the default font on a compact iPhone.
+struct FieldLimits {
+    header: usize,
+    primary: usize,
+    secondary: usize,
+    auxiliary: usize,
+    /// Secondary and auxiliary fields share a single row
+    combined: Option<usize>,
+    header_chars: usize,
+    primary_chars: usize,
+    row_chars: usize,
+}
+
+impl FieldLimits {
+    fn new(style: &PassStyle, images: &LayoutImages, square_barcode: bool) -> Self {
+        let mut limits = FieldLimits {
+            header: 3,
+            primary: 1,
+            secondary: 4,
+            auxiliary: 4,
+            combined: None,
+            header_chars: 18,
+            primary_chars: 18,
+            row_chars: 32,
+        };
+
+        match style {
+            PassStyle::BoardingPass(_) => {
+                limits.primary = 2;
+                limits.secondary = 5;
+                limits.auxiliary = 5;
+                limits.primary_chars = 16;
+            }
+            PassStyle::Coupon(_) | PassStyle::StoreCard(_) => limits.combined = Some(4),
+            PassStyle::EventTicket(_) => {
+                if images.strip {
+                    limits.combined = Some(4);
+                } else if images.thumbnail {
+                    limits.primary_chars = 12;
+                    limits.row_chars = 24;
+                }
+            }
+            PassStyle::Generic(_) => {
+                if square_barcode {
+                    limits.combined = Some(4);
+                }
+                if images.thumbnail {
+                    limits.primary_chars = 12;
+                }
+            }
+        }
+
+        limits
+    }
+}
+
+/// Fields Apple Wallet displays side by side
+struct Row<'a> {
+    description: &'static str,
+    fields: Vec<(String, &'a Field)>,
+    max: usize,
+    chars: usize,
+}
+
+impl PassStyle {
+    /// Check that all fields on the front of the pass are displayed by Apple Wallet
+    ///
+    /// Apple Wallet silently drops fields that exceed the limit of their section. The limits
+    /// depend on the style, the images of the pass and on whether it shows a square barcode
+    /// (`PKBarcodeFormatQR` or `PKBarcodeFormatAztec`). Also warns about values that will likely
+    /// be truncated and about images that are not displayed with this style.
+    pub fn lint_layout(&self, images: &LayoutImages, square_barcode: bool) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        self.lint_images(images, &mut diagnostics);
+
+        let limits = FieldLimits::new(self, images, square_barcode);
+        let key = self.key();
+        let groups = match self {
+            PassStyle::BoardingPass(boarding_pass) => boarding_pass.field_groups(),
+            PassStyle::Coupon(details)
+            | PassStyle::EventTicket(details)
+            | PassStyle::Generic(details)
+            | PassStyle::StoreCard(details) => details.field_groups(),
+        };
+
+        let section = |name: &str| {
+            groups
+                .iter()
+                .filter(|(group, _)| *group == name)
+                .flat_map(|(group, fields)| {
+                    fields
+                        .iter()
+                        .flatten()
+                        .enumerate()
+                        .map(move |(index, field)| (format!("{}.{}[{}]", key, group, index), field))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut rows = vec![
+            Row {
+                description: "header fields",
+                fields: section("headerFields"),
+                max: limits.header,
+                chars: limits.header_chars,
+            },
+            Row {
+                description: "primary fields",
+                fields: section("primaryFields"),
+                max: limits.primary,
+                chars: limits.primary_chars,
+            },
+        ];
+
+        let secondary = section("secondaryFields");
+        let auxiliary = section("auxiliaryFields");
+        match limits.combined {
+            Some(max) => rows.push(Row {
+                description: "secondary and auxiliary fields combined",
+                fields: secondary.into_iter().chain(auxiliary).collect(),
+                max,
+                chars: limits.row_chars,
+            }),
+            None => {
+                let (second_row, first_row) = auxiliary
+                    .into_iter()
+                    .partition(|(_, field)| field.row == Some(1));
+                rows.push(Row {
+                    description: "secondary fields",
+                    fields: secondary,
+                    max: limits.secondary,
+                    chars: limits.row_chars,
+                });
+                rows.push(Row {
+                    description: "auxiliary fields per row",
+                    fields: first_row,
+                    max: limits.auxiliary,
+                    chars: limits.row_chars,
+                });
+                rows.push(Row {
+                    description: "auxiliary fields per row",
+                    fields: second_row,
+                    max: limits.auxiliary,
+                    chars: limits.row_chars,
+                });
+            }
+        }
+
+        for row in rows {
+            lint_row(key, &row, &mut diagnostics);
+        }
+
+        diagnostics
+    }
+
+    /// Warn about images that are not displayed with this style
+    fn lint_images(&self, images: &LayoutImages, diagnostics: &mut Vec<Diagnostic>) {
+        let (strip, thumbnail, background) = match self {
+            PassStyle::BoardingPass(_) => (false, false, false),
+            PassStyle::Coupon(_) | PassStyle::StoreCard(_) => (true, false, false),
+            PassStyle::EventTicket(_) => (true, !images.strip, !images.strip),
+            PassStyle::Generic(_) => (false, true, false),
+        };
+
+        let ignored = [
+            ("strip.png", images.strip && !strip),
+            ("thumbnail.png", images.thumbnail && !thumbnail),
+            ("background.png", images.background && !background),
+        ];
+        for (image, is_ignored) in ignored {
+            if is_ignored {
+                diagnostics.push(Diagnostic::warning(
+                    "",
+                    &format!("{} is not displayed on this {} pass", image, self.key()),
+                ));
+            }
+        }
+    }
+}
+
+/// Warn about fields of a row that are not displayed and values that will likely be truncated
+fn lint_row(key: &str, row: &Row<'_>, diagnostics: &mut Vec<Diagnostic>) {
+    for (path, _) in row.fields.iter().skip(row.max) {
+        diagnostics.push(Diagnostic::warning(
+            path,
+            &format!(
+                "field is not displayed, the limit of {} on a {} pass is {}",
+                row.description, key, row.max
+            ),
+        ));
+    }
+
+    let displayed = row.fields.len().min(row.max);
+    if displayed == 0 {
+        return;
+    }
+
+    let chars = row.chars / displayed;
+    for (path, field) in row.fields.iter().take(row.max) {
+        // Dates and times are formatted by Apple Wallet
+        if field.date_style.is_some() || field.time_style.is_some() {
+            continue;
+        }
+
+        let length = match &field.value {
+            ValueUnion::String(value) => value.chars().count(),
+            ValueUnion::Double(value) => value.to_string().len(),
+        };
+        if length > chars {
+            diagnostics.push(Diagnostic::warning(
+                &format!("{}.value", path),
+                &format!(
+                    "value will likely be truncated, about {} characters fit",
+                    chars
+                ),
+            ));
+        }
+    }
+}
+
+impl Template {
+    /// Check the layout of the pass style like `PassStyle::lint_layout`, returns no diagnostics
+    /// if no style is set
+    pub fn lint_layout(&self, images: &LayoutImages) -> Vec<Diagnostic> {
+        let barcode = self
+            .barcodes
+            .iter()
+            .flatten()
+            .next()
+            .or(self.barcode.as_ref());
+        let square_barcode = barcode.is_some_and(|barcode| {
+            matches!(
+                barcode.format,
+                BarcodeFormat::PkBarcodeFormatQr | BarcodeFormat::PkBarcodeFormatAztec
+            )
+        });
+
+        match self.style() {
+            Some(style) => style.lint_layout(images, square_barcode),
+            None => Vec::new(),
+        }
+    }
+}
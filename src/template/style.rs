@@ -0,0 +1,79 @@
+use super::{BoardingPass, Details, Template};
+
+/// Style of a pass, which determines its layout and the json key of its fields
+///
+/// A pass has exactly one style. `Template::set_style` replaces any style that was set before.
+#[derive(Debug, Clone)]
+pub enum PassStyle {
+    /// Boarding pass, stored as `boardingPass`
+    BoardingPass(BoardingPass),
+    /// Coupon, stored as `coupon`
+    Coupon(Details),
+    /// Event ticket, stored as `eventTicket`
+    EventTicket(Details),
+    /// Generic pass, stored as `generic`
+    Generic(Details),
+    /// Store card, stored as `storeCard`
+    StoreCard(Details),
+}
+
+impl PassStyle {
+    /// Json key of the style, e.g. `eventTicket`
+    pub fn key(&self) -> &'static str {
+        match self {
+            PassStyle::BoardingPass(_) => "boardingPass",
+            PassStyle::Coupon(_) => "coupon",
+            PassStyle::EventTicket(_) => "eventTicket",
+            PassStyle::Generic(_) => "generic",
+            PassStyle::StoreCard(_) => "storeCard",
+        }
+    }
+}
+
+impl Template {
+    /// Style of the pass, `None` if no style is set
+    ///
+    /// Returns a copy of the style. If an invalid pass has multiple styles, the first one in json
+    /// key order is returned.
+    pub fn style(&self) -> Option<PassStyle> {
+        if let Some(boarding_pass) = &self.boarding_pass {
+            Some(PassStyle::BoardingPass(boarding_pass.clone()))
+        } else if let Some(coupon) = &self.coupon {
+            Some(PassStyle::Coupon(coupon.clone()))
+        } else if let Some(event_ticket) = &self.event_ticket {
+            Some(PassStyle::EventTicket(event_ticket.clone()))
+        } else if let Some(generic) = &self.generic {
+            Some(PassStyle::Generic(generic.clone()))
+        } else {
+            self.store_card.clone().map(PassStyle::StoreCard)
+        }
+    }
+
+    /// Set the style of the pass and remove all other styles
+    pub fn set_style(&mut self, style: PassStyle) {
+        self.take_style();
+
+        match style {
+            PassStyle::BoardingPass(boarding_pass) => self.boarding_pass = Some(boarding_pass),
+            PassStyle::Coupon(coupon) => self.coupon = Some(coupon),
+            PassStyle::EventTicket(event_ticket) => self.event_ticket = Some(event_ticket),
+            PassStyle::Generic(generic) => self.generic = Some(generic),
+            PassStyle::StoreCard(store_card) => self.store_card = Some(store_card),
+        }
+    }
+
+    /// Remove all styles of the pass and return the style `style` would have returned
+    pub fn take_style(&mut self) -> Option<PassStyle> {
+        let boarding_pass = self.boarding_pass.take().map(PassStyle::BoardingPass);
+        let coupon = self.coupon.take().map(PassStyle::Coupon);
+        let event_ticket = self.event_ticket.take().map(PassStyle::EventTicket);
+        let generic = self.generic.take().map(PassStyle::Generic);
+        let store_card = self.store_card.take().map(PassStyle::StoreCard);
+
+        boarding_pass
+            .or(coupon)
+            .or(event_ticket)
+            .or(generic)
+            .or(store_card)
+    }
+}
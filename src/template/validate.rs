@@ -0,0 +1,552 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use super::iso8601;
+use super::{Barcode, BarcodeFormat, Color, Field, Semantics, StyleScheme, Template, ValueUnion};
+
+/// Severity of a `Diagnostic`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The pass works, but a value is ignored or may not display as intended
+    Warning,
+    /// Apple Wallet rejects the pass or the value
+    Error,
+}
+
+/// Problem of a `Template` found by `Template::validate`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Severity of the problem
+    pub severity: Severity,
+    /// Json path of the problematic value, e.g. `eventTicket.primaryFields[0].key`. Empty for
+    /// problems of the pass as a whole.
+    pub path: String,
+    /// Description of the problem
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Create a new Instance with severity `Error`
+    pub fn error(path: &str, message: &str) -> Self {
+        Self {
+            severity: Severity::Error,
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Create a new Instance with severity `Warning`
+    pub fn warning(path: &str, message: &str) -> Self {
+        Self {
+            severity: Severity::Warning,
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Check if the severity is `Error`
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+
+        if self.path.is_empty() {
+            write!(f, "{}: {}", severity, self.message)
+        } else {
+            write!(f, "{} at {}: {}", severity, self.path, self.message)
+        }
+    }
+}
+
+/// Apple Wallet only uses this many locations and beacons
+const MAX_RELEVANCE_ENTRIES: usize = 10;
+
+/// Apple Wallet truncates longer NFC messages
+const MAX_NFC_MESSAGE_BYTES: usize = 64;
+
+/// Minimal length of the authentication token
+const MIN_AUTHENTICATION_TOKEN_LENGTH: usize = 16;
+
+impl Template {
+    /// Validate the pass against the rules of Apple Wallet
+    ///
+    /// Checks required keys, that exactly one pass style is set, that field keys are unique
+    /// across all field sections and the constraints of values. Errors describe passes or
+    /// values Apple Wallet rejects, warnings describe values that are ignored or may not be
+    /// displayed as intended.
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        self.validate_required_keys(&mut diagnostics);
+        self.validate_style(&mut diagnostics);
+        self.validate_fields(&mut diagnostics);
+        self.validate_barcodes(&mut diagnostics);
+        self.validate_relevance(&mut diagnostics);
+        self.validate_web_service(&mut diagnostics);
+        self.validate_colors(&mut diagnostics);
+
+        if let Some(nfc) = &self.nfc {
+            if nfc.message.len() > MAX_NFC_MESSAGE_BYTES {
+                diagnostics.push(Diagnostic::warning(
+                    "nfc.message",
+                    "NFC message is longer than 64 bytes and will be truncated",
+                ));
+            }
+        }
+
+        for (index, identifier) in self
+            .associated_store_identifiers
+            .iter()
+            .flatten()
+            .enumerate()
+        {
+            if identifier.fract() != 0.0 || *identifier < 0.0 {
+                diagnostics.push(Diagnostic::error(
+                    &format!("associatedStoreIdentifiers[{}]", index),
+                    "store identifier has to be a positive integer",
+                ));
+            }
+        }
+
+        for (path, value) in self.date_values() {
+            if !iso8601::is_date(value) {
+                diagnostics.push(Diagnostic::error(
+                    &path,
+                    &format!(
+                        "{:?} is not an ISO 8601 date and time with time zone",
+                        value
+                    ),
+                ));
+            }
+        }
+
+        diagnostics
+    }
+
+    fn validate_required_keys(&self, diagnostics: &mut Vec<Diagnostic>) {
+        let required = [
+            ("description", &self.description),
+            ("organizationName", &self.organization_name),
+            ("passTypeIdentifier", &self.pass_type_identifier),
+            ("serialNumber", &self.serial_number),
+        ];
+        for (key, value) in required {
+            if value.is_empty() {
+                diagnostics.push(Diagnostic::error(key, "required key is empty"));
+            }
+        }
+
+        if !self.pass_type_identifier.is_empty() && !self.pass_type_identifier.starts_with("pass.")
+        {
+            diagnostics.push(Diagnostic::warning(
+                "passTypeIdentifier",
+                "pass type identifiers issued by Apple start with \"pass.\"",
+            ));
+        }
+
+        match &self.format_version {
+            None => diagnostics.push(Diagnostic::error(
+                "formatVersion",
+                "required key is missing, set it to 1",
+            )),
+            Some(format_version) if format_version.as_u64() != Some(1) => diagnostics.push(
+                Diagnostic::error("formatVersion", "format version has to be 1"),
+            ),
+            Some(_) => {}
+        }
+
+        if self
+            .team_identifier
+            .as_deref()
+            .unwrap_or_default()
+            .is_empty()
+        {
+            diagnostics.push(Diagnostic::error(
+                "teamIdentifier",
+                "required key is missing",
+            ));
+        }
+    }
+
+    fn validate_style(&self, diagnostics: &mut Vec<Diagnostic>) {
+        let styles = self.style_keys();
+
+        match styles.len() {
+            0 => diagnostics.push(Diagnostic::error(
+                "",
+                "pass has no style, set one of boardingPass, coupon, eventTicket, generic or \
+                 storeCard",
+            )),
+            1 => {}
+            _ => {
+                for key in &styles[1..] {
+                    diagnostics.push(Diagnostic::error(
+                        key,
+                        &format!("pass has more than one style, {} is already set", styles[0]),
+                    ));
+                }
+            }
+        }
+
+        let styles = [
+            ("coupon", &self.coupon),
+            ("eventTicket", &self.event_ticket),
+            ("generic", &self.generic),
+            ("storeCard", &self.store_card),
+        ];
+        for (key, details) in styles {
+            if let Some(details) = details {
+                if details.transit_type.is_some() {
+                    diagnostics.push(Diagnostic::warning(
+                        &format!("{}.transitType", key),
+                        "transit type is only used by boarding passes",
+                    ));
+                }
+                if key != "eventTicket" && details.additional_info_fields.is_some() {
+                    diagnostics.push(Diagnostic::warning(
+                        &format!("{}.additionalInfoFields", key),
+                        "additional info fields are only used by poster event tickets",
+                    ));
+                }
+            }
+        }
+
+        let poster = self
+            .preferred_style_schemes
+            .iter()
+            .flatten()
+            .any(|scheme| matches!(scheme, StyleScheme::PosterEventTicket));
+        if poster && self.event_ticket.is_none() {
+            diagnostics.push(Diagnostic::error(
+                "preferredStyleSchemes",
+                "poster event ticket style requires an eventTicket",
+            ));
+        }
+
+        if self.grouping_identifier.is_some()
+            && self.event_ticket.is_none()
+            && self.boarding_pass.is_none()
+        {
+            diagnostics.push(Diagnostic::warning(
+                "groupingIdentifier",
+                "grouping identifier is only used by event tickets and boarding passes",
+            ));
+        }
+    }
+
+    /// Json keys of all set pass styles
+    fn style_keys(&self) -> Vec<&'static str> {
+        let styles = [
+            ("boardingPass", self.boarding_pass.is_some()),
+            ("coupon", self.coupon.is_some()),
+            ("eventTicket", self.event_ticket.is_some()),
+            ("generic", self.generic.is_some()),
+            ("storeCard", self.store_card.is_some()),
+        ];
+
+        styles
+            .iter()
+            .filter(|(_, is_set)| *is_set)
+            .map(|(key, _)| *key)
+            .collect()
+    }
+
+    fn validate_fields(&self, diagnostics: &mut Vec<Diagnostic>) {
+        let mut groups = Vec::new();
+        if let Some(boarding_pass) = &self.boarding_pass {
+            groups.extend(
+                boarding_pass
+                    .field_groups()
+                    .into_iter()
+                    .map(|(key, fields)| (format!("boardingPass.{}", key), fields)),
+            );
+        }
+        let styles = [
+            ("coupon", &self.coupon),
+            ("eventTicket", &self.event_ticket),
+            ("generic", &self.generic),
+            ("storeCard", &self.store_card),
+        ];
+        for (style, details) in styles {
+            if let Some(details) = details {
+                groups.extend(
+                    details
+                        .field_groups()
+                        .into_iter()
+                        .map(|(key, fields)| (format!("{}.{}", style, key), fields)),
+                );
+            }
+        }
+
+        let mut keys = HashSet::new();
+        for (group, fields) in groups {
+            for (index, field) in fields.iter().flatten().enumerate() {
+                let path = format!("{}[{}]", group, index);
+
+                if field.key.is_empty() {
+                    diagnostics.push(Diagnostic::error(
+                        &format!("{}.key", path),
+                        "field key is empty",
+                    ));
+                } else if !keys.insert(field.key.as_str()) {
+                    diagnostics.push(Diagnostic::error(
+                        &format!("{}.key", path),
+                        &format!("field key {:?} is not unique within the pass", field.key),
+                    ));
+                }
+
+                validate_field(field, &group, &path, diagnostics);
+            }
+        }
+    }
+
+    fn validate_barcodes(&self, diagnostics: &mut Vec<Diagnostic>) {
+        if let Some(barcode) = &self.barcode {
+            diagnostics.push(Diagnostic::warning(
+                "barcode",
+                "barcode is deprecated since iOS 9.0, use barcodes instead",
+            ));
+            if matches!(barcode.format, BarcodeFormat::PkBarcodeFormatCode128) {
+                diagnostics.push(Diagnostic::error(
+                    "barcode.format",
+                    "PKBarcodeFormatCode128 is only allowed in barcodes",
+                ));
+            }
+            validate_barcode(barcode, "barcode", diagnostics);
+        }
+
+        for (index, barcode) in self.barcodes.iter().flatten().enumerate() {
+            validate_barcode(barcode, &format!("barcodes[{}]", index), diagnostics);
+        }
+    }
+
+    fn validate_relevance(&self, diagnostics: &mut Vec<Diagnostic>) {
+        let beacons = self.beacons.as_ref().map_or(0, Vec::len);
+        if beacons > MAX_RELEVANCE_ENTRIES {
+            diagnostics.push(Diagnostic::warning(
+                "beacons",
+                "only the first 10 beacons are used",
+            ));
+        }
+        for (index, beacon) in self.beacons.iter().flatten().enumerate() {
+            if !is_uuid(&beacon.proximity_uuid) {
+                diagnostics.push(Diagnostic::error(
+                    &format!("beacons[{}].proximityUUID", index),
+                    "proximity UUID is not a UUID",
+                ));
+            }
+            let ranges = [("major", beacon.major), ("minor", beacon.minor)];
+            for (key, value) in ranges {
+                if value.is_some_and(|value| !(0..=0xffff).contains(&value)) {
+                    diagnostics.push(Diagnostic::error(
+                        &format!("beacons[{}].{}", index, key),
+                        "value has to be a 16-bit unsigned integer",
+                    ));
+                }
+            }
+        }
+
+        let locations = self.locations.as_ref().map_or(0, Vec::len);
+        if locations > MAX_RELEVANCE_ENTRIES {
+            diagnostics.push(Diagnostic::warning(
+                "locations",
+                "only the first 10 locations are used",
+            ));
+        }
+        for (index, location) in self.locations.iter().flatten().enumerate() {
+            if !(-90.0..=90.0).contains(&location.latitude) {
+                diagnostics.push(Diagnostic::error(
+                    &format!("locations[{}].latitude", index),
+                    "latitude has to be between -90 and 90 degrees",
+                ));
+            }
+            if !(-180.0..=180.0).contains(&location.longitude) {
+                diagnostics.push(Diagnostic::error(
+                    &format!("locations[{}].longitude", index),
+                    "longitude has to be between -180 and 180 degrees",
+                ));
+            }
+        }
+
+        if self
+            .max_distance
+            .is_some_and(|max_distance| max_distance < 0.0)
+        {
+            diagnostics.push(Diagnostic::error(
+                "maxDistance",
+                "maximum distance has to be positive",
+            ));
+        }
+
+        for (index, relevant_date) in self.relevant_dates.iter().flatten().enumerate() {
+            let has_date = relevant_date.date.is_some();
+            let has_interval = relevant_date.start_date.is_some();
+            if has_date == has_interval {
+                diagnostics.push(Diagnostic::error(
+                    &format!("relevantDates[{}]", index),
+                    "relevant date needs either a date or a start date",
+                ));
+            }
+            if relevant_date.end_date.is_some() && !has_interval {
+                diagnostics.push(Diagnostic::error(
+                    &format!("relevantDates[{}].endDate", index),
+                    "end date requires a start date",
+                ));
+            }
+        }
+    }
+
+    fn validate_colors(&self, diagnostics: &mut Vec<Diagnostic>) {
+        let colors = [
+            ("backgroundColor", &self.background_color),
+            ("footerBackgroundColor", &self.footer_background_color),
+            ("foregroundColor", &self.foreground_color),
+            ("labelColor", &self.label_color),
+        ];
+        for (key, color) in colors {
+            validate_color(color.as_ref(), key, diagnostics);
+        }
+
+        if let Some(semantics) = &self.semantics {
+            validate_semantics(semantics, "semantics", diagnostics);
+        }
+    }
+
+    fn validate_web_service(&self, diagnostics: &mut Vec<Diagnostic>) {
+        match (&self.web_service_url, &self.authentication_token) {
+            (Some(_), None) => diagnostics.push(Diagnostic::error(
+                "authenticationToken",
+                "authentication token is required for the web service",
+            )),
+            (None, Some(_)) => diagnostics.push(Diagnostic::warning(
+                "authenticationToken",
+                "authentication token is only used with a web service",
+            )),
+            _ => {}
+        }
+
+        if let Some(token) = &self.authentication_token {
+            if token.chars().count() < MIN_AUTHENTICATION_TOKEN_LENGTH {
+                diagnostics.push(Diagnostic::error(
+                    "authenticationToken",
+                    "authentication token has to be at least 16 characters long",
+                ));
+            }
+        }
+
+        if let Some(url) = &self.web_service_url {
+            if !url.starts_with("https://") {
+                diagnostics.push(Diagnostic::warning(
+                    "webServiceURL",
+                    "web service has to use HTTPS, HTTP only works on development devices",
+                ));
+            }
+        }
+    }
+}
+
+fn validate_field(field: &Field, group: &str, path: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let is_number = matches!(field.value, ValueUnion::Double(_));
+
+    if field.text_alignment.is_some()
+        && (group.ends_with("primaryFields") || group.ends_with("backFields"))
+    {
+        diagnostics.push(Diagnostic::warning(
+            &format!("{}.textAlignment", path),
+            "text alignment is not allowed for primary fields and back fields",
+        ));
+    }
+
+    if !is_number && field.currency_code.is_some() {
+        diagnostics.push(Diagnostic::error(
+            &format!("{}.currencyCode", path),
+            "currency code requires a number value",
+        ));
+    }
+    if !is_number && field.number_style.is_some() {
+        diagnostics.push(Diagnostic::error(
+            &format!("{}.numberStyle", path),
+            "number style requires a number value",
+        ));
+    }
+    if field.currency_code.is_some() && field.number_style.is_some() {
+        diagnostics.push(Diagnostic::warning(
+            &format!("{}.numberStyle", path),
+            "number style is ignored for values with a currency code",
+        ));
+    }
+    if is_number && (field.date_style.is_some() || field.time_style.is_some()) {
+        diagnostics.push(Diagnostic::error(
+            &format!("{}.value", path),
+            "date and time styles require an ISO 8601 date value",
+        ));
+    }
+
+    if field.row.is_some() && !group.ends_with("auxiliaryFields") {
+        diagnostics.push(Diagnostic::warning(
+            &format!("{}.row", path),
+            "row is only used by auxiliary fields",
+        ));
+    }
+    if field.row.is_some_and(|row| row != 0 && row != 1) {
+        diagnostics.push(Diagnostic::error(
+            &format!("{}.row", path),
+            "row has to be 0 or 1",
+        ));
+    }
+
+    if let Some(semantics) = &field.semantics {
+        validate_semantics(semantics, &format!("{}.semantics", path), diagnostics);
+    }
+}
+
+fn validate_semantics(semantics: &Semantics, path: &str, diagnostics: &mut Vec<Diagnostic>) {
+    for (index, seat) in semantics.seats.iter().flatten().enumerate() {
+        validate_color(
+            seat.seat_section_color.as_ref(),
+            &format!("{}.seats[{}].seatSectionColor", path, index),
+            diagnostics,
+        );
+    }
+}
+
+fn validate_color(color: Option<&Color>, path: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if let Some(Color::Other(value)) = color {
+        diagnostics.push(Diagnostic::warning(
+            path,
+            &format!(
+                "{:?} is not a CSS-style RGB triple like rgb(23, 187, 82) and is ignored",
+                value
+            ),
+        ));
+    }
+}
+
+fn validate_barcode(barcode: &Barcode, path: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if barcode.message.is_empty() {
+        diagnostics.push(Diagnostic::error(
+            &format!("{}.message", path),
+            "barcode message is empty",
+        ));
+    }
+    if barcode.message_encoding.is_empty() {
+        diagnostics.push(Diagnostic::error(
+            &format!("{}.messageEncoding", path),
+            "barcode message encoding is empty, e.g. use iso-8859-1",
+        ));
+    }
+}
+
+/// Check if the value is a UUID like `E2C56DB5-DFFB-48D2-B060-D0F5A71096E0`
+fn is_uuid(value: &str) -> bool {
+    let groups = value.split('-').map(str::len).collect::<Vec<_>>();
+
+    groups == [8, 4, 4, 4, 12] && value.chars().all(|c| c == '-' || c.is_ascii_hexdigit())
+}
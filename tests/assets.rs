@@ -0,0 +1,153 @@
+//! Assets set or removed on a `Pass` are part of the exported archive
+
+mod common;
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{Cursor, Read};
+use wallet_pass::sign::SigningIdentity;
+use wallet_pass::Pass;
+
+use common::KeyType;
+
+/// Files of the exported archive without directories, manifest and signature
+fn export(pass: &Pass, identity: &SigningIdentity) -> BTreeMap<String, Vec<u8>> {
+    let archive = pass.export_to_vec(identity).unwrap();
+    let mut archive = zip::ZipArchive::new(Cursor::new(archive)).unwrap();
+    let mut files = BTreeMap::new();
+
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index).unwrap();
+        let name = file.name().to_owned();
+        if name.ends_with('/') || name == "signature" {
+            continue;
+        }
+        let mut content = Vec::new();
+        file.read_to_end(&mut content).unwrap();
+        files.insert(name, content);
+    }
+
+    // Every file is listed in the manifest
+    let manifest: BTreeMap<String, String> =
+        serde_json::from_slice(&files.remove("manifest.json").unwrap()).unwrap();
+    assert_eq!(
+        manifest.keys().collect::<Vec<_>>(),
+        files.keys().collect::<Vec<_>>()
+    );
+
+    files
+}
+
+fn pass_directory(name: &str) -> std::path::PathBuf {
+    let pass_path = common::temp_dir(name);
+    fs::write(
+        pass_path.join("pass.json"),
+        serde_json::to_vec(&common::template()).unwrap(),
+    )
+    .unwrap();
+    fs::write(pass_path.join("icon.png"), common::png(29, 29)).unwrap();
+    fs::write(pass_path.join("logo.png"), common::png(160, 50)).unwrap();
+
+    pass_path
+}
+
+#[test]
+fn set_asset_adds_and_replaces_files() {
+    let (identity, _) = common::identity(KeyType::Rsa);
+    let pass_path = pass_directory("assets-set");
+    let mut pass = Pass::from_path(&pass_path).unwrap();
+
+    pass.set_asset("logo.png", common::png(320, 100)).unwrap();
+    pass.set_asset("de.lproj/logo.png", common::png(150, 50))
+        .unwrap();
+    let files = export(&pass, &identity);
+
+    assert_eq!(
+        files.keys().collect::<Vec<_>>(),
+        ["de.lproj/logo.png", "icon.png", "logo.png", "pass.json"]
+    );
+    assert_eq!(files["logo.png"], common::png(320, 100));
+    assert_eq!(files["de.lproj/logo.png"], common::png(150, 50));
+    assert_eq!(files["icon.png"], common::png(29, 29));
+
+    // The pass directory is left untouched
+    assert_eq!(
+        fs::read(pass_path.join("logo.png")).unwrap(),
+        common::png(160, 50)
+    );
+    assert!(!pass_path.join("de.lproj").exists());
+    fs::remove_dir_all(pass_path).unwrap();
+}
+
+#[test]
+fn remove_asset_leaves_out_files() {
+    let (identity, _) = common::identity(KeyType::Rsa);
+    let pass_path = pass_directory("assets-remove");
+    let mut pass = Pass::from_path(&pass_path).unwrap();
+    pass.set_asset("strip.png", common::png(375, 123)).unwrap();
+
+    pass.remove_asset("logo.png");
+    pass.remove_asset("strip.png");
+    let files = export(&pass, &identity);
+
+    assert_eq!(files.keys().collect::<Vec<_>>(), ["icon.png", "pass.json"]);
+    assert!(pass_path.join("logo.png").exists());
+    assert_eq!(
+        pass.assets().unwrap().keys().collect::<Vec<_>>(),
+        ["icon.png"]
+    );
+    fs::remove_dir_all(pass_path).unwrap();
+}
+
+#[test]
+fn set_asset_after_remove_asset() {
+    let (identity, _) = common::identity(KeyType::Rsa);
+    let pass_path = pass_directory("assets-restore");
+    let mut pass = Pass::from_path(&pass_path).unwrap();
+
+    pass.remove_asset("logo.png");
+    pass.set_asset("logo.png", common::png(320, 100)).unwrap();
+    let files = export(&pass, &identity);
+
+    assert_eq!(files["logo.png"], common::png(320, 100));
+    fs::remove_dir_all(pass_path).unwrap();
+}
+
+#[test]
+fn assets_of_pass_without_directory() {
+    let (identity, _) = common::identity(KeyType::Rsa);
+    let pass_path = pass_directory("assets-pkpass");
+    let archive = Pass::from_path(&pass_path)
+        .unwrap()
+        .export_to_vec(&identity)
+        .unwrap();
+    fs::remove_dir_all(pass_path).unwrap();
+
+    let mut pass = Pass::from_pkpass(Cursor::new(archive)).unwrap();
+    pass.remove_asset("logo.png");
+    pass.set_asset("thumbnail.png", common::png(90, 90))
+        .unwrap();
+    let files = export(&pass, &identity);
+
+    assert_eq!(
+        files.keys().collect::<Vec<_>>(),
+        ["icon.png", "pass.json", "thumbnail.png"]
+    );
+    assert_eq!(files["thumbnail.png"], common::png(90, 90));
+}
+
+#[test]
+fn template_changes_are_exported() {
+    let (identity, _) = common::identity(KeyType::Rsa);
+    let pass_path = pass_directory("assets-template");
+    let mut pass = Pass::from_path(&pass_path).unwrap();
+
+    pass.template.serial_number = "2".into();
+    // The pass.json is represented by the template and can not be removed
+    pass.remove_asset("pass.json");
+    let files = export(&pass, &identity);
+
+    let template: serde_json::Value = serde_json::from_slice(&files["pass.json"]).unwrap();
+    assert_eq!(template["serialNumber"], "2");
+    fs::remove_dir_all(pass_path).unwrap();
+}
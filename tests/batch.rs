@@ -0,0 +1,112 @@
+//! Batch export of personalized passes
+
+mod common;
+
+use std::fs;
+use std::io::Cursor;
+use std::sync::Mutex;
+use wallet_pass::template::Template;
+use wallet_pass::{Error, Pass};
+
+use common::KeyType;
+
+/// Pass with an icon in memory
+fn pass() -> Pass {
+    let mut pass = Pass::from_template(&common::template(), common::temp_dir("batch-assets"));
+    pass.set_asset("icon.png", common::png(29, 29)).unwrap();
+
+    pass
+}
+
+/// Mutations setting the given serial numbers
+fn serial_numbers<'a>(
+    serial_numbers: &'a [&'a str],
+) -> impl Iterator<Item = impl FnOnce(&mut Template) + Send + 'a> + Send + 'a {
+    serial_numbers.iter().map(|serial_number| {
+        move |template: &mut Template| template.serial_number = serial_number.to_string()
+    })
+}
+
+#[test]
+fn export_batch_personalizes_every_pass() {
+    let (identity, _) = common::identity(KeyType::Rsa);
+    let exported = Mutex::new(Vec::new());
+
+    pass()
+        .export_batch(
+            &identity,
+            serial_numbers(&["a", "b", "c"]),
+            |index, template, archive| {
+                let pass = Pass::from_pkpass(Cursor::new(archive))?;
+                assert_eq!(pass.template.serial_number, template.serial_number);
+                exported
+                    .lock()
+                    .unwrap()
+                    .push((index, template.serial_number.clone()));
+
+                Ok(())
+            },
+        )
+        .unwrap();
+
+    let mut exported = exported.into_inner().unwrap();
+    exported.sort();
+    assert_eq!(
+        exported,
+        vec![(0, "a".into()), (1, "b".into()), (2, "c".into())]
+    );
+}
+
+#[test]
+fn export_batch_to_directory_names_archives_by_serial_number() {
+    let (identity, _) = common::identity(KeyType::Rsa);
+    let output_path = common::temp_dir("batch-output");
+
+    pass()
+        .export_batch_to_directory(&identity, serial_numbers(&["a", "b"]), &output_path)
+        .unwrap();
+
+    let mut names = fs::read_dir(&output_path)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, vec!["a.pkpass", "b.pkpass"]);
+
+    fs::remove_dir_all(output_path).unwrap();
+}
+
+#[test]
+fn export_batch_to_directory_rejects_duplicate_serial_numbers() {
+    let (identity, _) = common::identity(KeyType::Rsa);
+    let output_path = common::temp_dir("batch-duplicate");
+
+    let result =
+        pass().export_batch_to_directory(&identity, serial_numbers(&["a", "a"]), &output_path);
+
+    assert!(
+        matches!(result, Err(Error::DuplicateSerialNumber(serial_number)) if serial_number == "a")
+    );
+    fs::remove_dir_all(output_path).unwrap();
+}
+
+#[test]
+fn export_batch_to_directory_rejects_invalid_serial_numbers() {
+    let (identity, _) = common::identity(KeyType::Rsa);
+    let output_path = common::temp_dir("batch-invalid");
+
+    for serial_number in [".hidden", "a/b", "a\\b"] {
+        let result = pass().export_batch_to_directory(
+            &identity,
+            serial_numbers(&[serial_number]),
+            &output_path,
+        );
+
+        assert!(
+            matches!(result, Err(Error::InvalidSerialNumber(_))),
+            "{:?}",
+            serial_number
+        );
+    }
+    fs::remove_dir_all(output_path).unwrap();
+}
@@ -0,0 +1,43 @@
+//! Validity checks of `CertificateInfo`
+
+use std::time::{Duration, SystemTime};
+use wallet_pass::sign::CertificateInfo;
+
+fn certificate_info(not_before: SystemTime, not_after: SystemTime) -> CertificateInfo {
+    CertificateInfo {
+        pass_type_identifier: None,
+        team_identifier: None,
+        common_name: None,
+        organization_name: None,
+        not_before,
+        not_after,
+    }
+}
+
+#[test]
+fn expires_within() {
+    let now = SystemTime::now();
+    let info = certificate_info(now, now + Duration::from_secs(30 * 86_400));
+
+    assert!(!info.is_expired());
+    assert!(!info.expires_within(Duration::from_secs(86_400)));
+    assert!(info.expires_within(Duration::from_secs(60 * 86_400)));
+}
+
+#[test]
+fn expires_within_overflowing_duration() {
+    let now = SystemTime::now();
+    let info = certificate_info(now, now + Duration::from_secs(30 * 86_400));
+
+    assert!(info.expires_within(Duration::MAX));
+}
+
+#[test]
+fn is_valid_at() {
+    let now = SystemTime::now();
+    let info = certificate_info(now, now + Duration::from_secs(60));
+
+    assert!(info.is_valid_at(now + Duration::from_secs(30)));
+    assert!(!info.is_valid_at(now - Duration::from_secs(30)));
+    assert!(!info.is_valid_at(now + Duration::from_secs(90)));
+}
@@ -0,0 +1,93 @@
+//! Colors of a pass
+
+use wallet_pass::template::{Color, Details, Severity, Template};
+
+#[test]
+fn parses_rgb_triples_and_hex_colors() {
+    assert_eq!(
+        "rgb(23, 187, 82)".parse::<Color>().unwrap(),
+        Color::new(23, 187, 82)
+    );
+    assert_eq!(
+        "rgb(23,187,82)".parse::<Color>().unwrap(),
+        Color::new(23, 187, 82)
+    );
+    assert_eq!("#17bb52".parse::<Color>().unwrap(), Color::new(23, 187, 82));
+    assert!("rgb(256, 0, 0)".parse::<Color>().is_err());
+    assert!("rgba(1, 2, 3, 0.5)".parse::<Color>().is_err());
+}
+
+#[test]
+fn from_hex() {
+    assert_eq!(Color::from_hex("#17bb52").unwrap(), Color::new(23, 187, 82));
+    assert_eq!(Color::from_hex("17BB52").unwrap(), Color::new(23, 187, 82));
+    assert_eq!(
+        Color::from_hex("#1b5").unwrap(),
+        Color::new(0x11, 0xbb, 0x55)
+    );
+    assert!(Color::from_hex("#17bb5").is_err());
+    assert!(Color::from_hex("#17bb5g").is_err());
+    assert!(Color::from_hex("").is_err());
+}
+
+#[test]
+fn to_hex() {
+    assert_eq!(Color::new(23, 187, 82).to_hex().unwrap(), "#17bb52");
+    assert_eq!(Color::new(0, 0, 0).to_hex().unwrap(), "#000000");
+    assert_eq!(Color::Other("red".into()).to_hex(), None);
+}
+
+#[test]
+fn contrast_ratio() {
+    let black = Color::new(0, 0, 0);
+    let white = Color::new(255, 255, 255);
+
+    assert!((black.contrast_ratio(&white).unwrap() - 21.0).abs() < 1e-9);
+    assert!((white.contrast_ratio(&black).unwrap() - 21.0).abs() < 1e-9);
+    assert!((white.contrast_ratio(&white).unwrap() - 1.0).abs() < 1e-9);
+    // #767676 is the lightest gray with a contrast ratio of 4.5 to white
+    let gray = Color::new(0x76, 0x76, 0x76);
+    assert!(gray.contrast_ratio(&white).unwrap() >= 4.5);
+    assert_eq!(black.contrast_ratio(&Color::Other("red".into())), None);
+}
+
+#[test]
+fn serializes_as_rgb_triple() {
+    let json = serde_json::to_string(&Color::new(23, 187, 82)).unwrap();
+
+    assert_eq!(json, "\"rgb(23, 187, 82)\"");
+    assert_eq!(
+        serde_json::from_str::<Color>("\"#17bb52\"").unwrap(),
+        Color::new(23, 187, 82)
+    );
+}
+
+#[test]
+fn keeps_unknown_colors() {
+    let color: Color = serde_json::from_str("\"rgba(1,2,3,0.5)\"").unwrap();
+
+    assert_eq!(color, Color::Other("rgba(1,2,3,0.5)".into()));
+    assert_eq!(
+        serde_json::to_string(&color).unwrap(),
+        "\"rgba(1,2,3,0.5)\""
+    );
+}
+
+#[test]
+fn validate_warns_about_unknown_colors() {
+    let mut template = Template::new("Test", "wallet-pass", "pass.com.example.test", "1");
+    template.generic(Details::new());
+    template.foreground_color(Color::new(255, 255, 255));
+    template.background_color(Color::Other("rgba(1,2,3,0.5)".into()));
+
+    let diagnostics = template
+        .validate()
+        .into_iter()
+        .filter(|diagnostic| diagnostic.path.ends_with("Color"))
+        .collect::<Vec<_>>();
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].path, "backgroundColor");
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+    assert_eq!(template.contrast_ratio(), None);
+}
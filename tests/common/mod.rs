@@ -0,0 +1,129 @@
+//! Throwaway certificate chains for signing passes in tests
+
+use openssl::asn1::{Asn1Integer, Asn1Time};
+use openssl::bn::BigNum;
+use openssl::ec::{EcGroup, EcKey};
+use openssl::hash::MessageDigest;
+use openssl::nid::Nid;
+use openssl::pkey::{PKey, Private};
+use openssl::rsa::Rsa;
+use openssl::x509::extension::BasicConstraints;
+use openssl::x509::{X509Name, X509};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use wallet_pass::sign::SigningIdentity;
+use wallet_pass::template::{Details, Template};
+
+pub const PASS_TYPE_IDENTIFIER: &str = "pass.com.example.test";
+
+/// Key type of the pass type certificate
+#[allow(dead_code)]
+pub enum KeyType {
+    Rsa,
+    Ec,
+}
+
+/// Certificate valid from 2020 to 2060
+fn certificate(
+    subject: &X509Name,
+    issuer: &X509Name,
+    public_key: &PKey<Private>,
+    issuer_key: &PKey<Private>,
+    serial_number: u32,
+    is_ca: bool,
+) -> X509 {
+    let mut builder = X509::builder().unwrap();
+    builder.set_version(2).unwrap();
+    let serial_number = Asn1Integer::from_bn(&BigNum::from_u32(serial_number).unwrap()).unwrap();
+    builder.set_serial_number(&serial_number).unwrap();
+    builder.set_subject_name(subject).unwrap();
+    builder.set_issuer_name(issuer).unwrap();
+    builder.set_pubkey(public_key).unwrap();
+    builder
+        .set_not_before(&Asn1Time::from_str("20200101000000Z").unwrap())
+        .unwrap();
+    builder
+        .set_not_after(&Asn1Time::from_str("20600101000000Z").unwrap())
+        .unwrap();
+    if is_ca {
+        builder
+            .append_extension(BasicConstraints::new().critical().ca().build().unwrap())
+            .unwrap();
+    }
+    builder.sign(issuer_key, MessageDigest::sha256()).unwrap();
+
+    builder.build()
+}
+
+/// Create a signing identity with a pass type certificate issued by a throwaway wwdr
+/// intermediate certificate, which is returned as well
+#[allow(dead_code)]
+pub fn identity(key_type: KeyType) -> (SigningIdentity, X509) {
+    let ca_key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+    let mut ca_name = X509Name::builder().unwrap();
+    ca_name
+        .append_entry_by_nid(Nid::COMMONNAME, "Test WWDR")
+        .unwrap();
+    let ca_name = ca_name.build();
+    let ca = certificate(&ca_name, &ca_name, &ca_key, &ca_key, 1, true);
+
+    let key = match key_type {
+        KeyType::Rsa => PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap(),
+        KeyType::Ec => {
+            let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+            PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap()
+        }
+    };
+    let mut name = X509Name::builder().unwrap();
+    name.append_entry_by_nid(Nid::USERID, PASS_TYPE_IDENTIFIER)
+        .unwrap();
+    name.append_entry_by_nid(Nid::ORGANIZATIONALUNITNAME, "ABCDE12345")
+        .unwrap();
+    name.append_entry_by_nid(Nid::COMMONNAME, "Pass Type ID: pass.com.example.test")
+        .unwrap();
+    let name = name.build();
+    let pass_certificate = certificate(&name, &ca_name, &key, &ca_key, 2, false);
+
+    let identity = SigningIdentity::new(pass_certificate, key, ca.clone()).unwrap();
+
+    (identity, ca)
+}
+
+/// Template of a generic pass for the throwaway pass type certificate
+#[allow(dead_code)]
+pub fn template() -> Template {
+    let mut template = Template::new("Test", "wallet-pass", PASS_TYPE_IDENTIFIER, "1");
+    template.generic(Details::new());
+
+    template
+}
+
+/// Header of a PNG image with the given size in pixels, enough for the checks of this crate
+#[allow(dead_code)]
+pub fn png(width: u32, height: u32) -> Vec<u8> {
+    [
+        &b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR"[..],
+        &width.to_be_bytes(),
+        &height.to_be_bytes(),
+        b"\x08\x06\x00\x00\x00",
+    ]
+    .concat()
+}
+
+/// Create an empty directory for a test below the temporary directory
+#[allow(dead_code)]
+pub fn temp_dir(name: &str) -> std::path::PathBuf {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let path = std::env::temp_dir().join(format!(
+        "wallet-pass-{}-{}-{}",
+        name,
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    if path.exists() {
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+    std::fs::create_dir_all(&path).unwrap();
+
+    path
+}
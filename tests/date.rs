@@ -0,0 +1,128 @@
+//! Typed dates with the `chrono` feature
+
+#![cfg(feature = "chrono")]
+
+use chrono::{FixedOffset, TimeZone, Utc};
+use wallet_pass::template::{format_date, parse_date, Details, RelevantDate, Template};
+use wallet_pass::Error;
+
+fn template() -> Template {
+    let mut template = Template::new("Test", "wallet-pass", "pass.com.example.test", "1");
+    template.generic(Details::new());
+
+    template
+}
+
+#[test]
+fn format_dates() {
+    let offset = FixedOffset::east_opt(3600).unwrap();
+
+    assert_eq!(
+        format_date(&offset.with_ymd_and_hms(2024, 11, 1, 18, 30, 0).unwrap()),
+        "2024-11-01T18:30:00+01:00"
+    );
+    assert_eq!(
+        format_date(&Utc.with_ymd_and_hms(2024, 11, 1, 17, 30, 0).unwrap()),
+        "2024-11-01T17:30:00Z"
+    );
+
+    // Fractions of seconds are dropped
+    let date = Utc.timestamp_opt(1_730_482_200, 250_000_000).unwrap();
+    assert_eq!(format_date(&date), "2024-11-01T17:30:00Z");
+}
+
+#[test]
+fn parse_dates() {
+    let expected = FixedOffset::east_opt(3600)
+        .unwrap()
+        .with_ymd_and_hms(2024, 11, 1, 18, 30, 0)
+        .unwrap();
+
+    for value in [
+        "2024-11-01T18:30:00+01:00",
+        "2024-11-01T18:30+01:00",
+        "2024-11-01T17:30:00Z",
+        "2024-11-01T17:30Z",
+        "2024-11-01T12:30:00-05:00",
+    ] {
+        assert_eq!(parse_date(value).unwrap(), expected, "{}", value);
+    }
+    assert_eq!(
+        parse_date("2024-11-01T18:30:00.250+01:00")
+            .unwrap()
+            .timestamp_subsec_millis(),
+        250
+    );
+}
+
+#[test]
+fn parse_invalid_dates() {
+    for value in [
+        "",
+        "2024-11-01",
+        "2024-11-01T18:30:00",
+        "2024-13-01T18:30:00Z",
+        "2023-02-29T18:30Z",
+        "01.11.2024 18:30",
+    ] {
+        assert!(
+            matches!(
+                parse_date(value),
+                Err(Error::InvalidDate { value: invalid, .. }) if invalid == value
+            ),
+            "{}",
+            value
+        );
+    }
+}
+
+#[test]
+fn format_and_parse_round_trip() {
+    let date = FixedOffset::west_opt(7 * 3600)
+        .unwrap()
+        .with_ymd_and_hms(2025, 3, 9, 1, 59, 59)
+        .unwrap();
+
+    assert_eq!(parse_date(&format_date(&date)).unwrap(), date);
+}
+
+#[test]
+fn typed_template_dates() {
+    let start = Utc.with_ymd_and_hms(2024, 11, 1, 17, 30, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 11, 1, 20, 0, 0).unwrap();
+    let mut template = template();
+    template.relevant_date_time(&start);
+    template.expiration_date_time(&end);
+    template.add_relevant_date(RelevantDate::from_interval(&start, &end));
+
+    assert_eq!(
+        template.relevant_date.as_deref(),
+        Some("2024-11-01T17:30:00Z")
+    );
+    assert_eq!(template.parse_relevant_date().unwrap(), Some(start.into()));
+    assert_eq!(template.parse_expiration_date().unwrap(), Some(end.into()));
+    let relevant_date = &template.relevant_dates.as_ref().unwrap()[0];
+    assert_eq!(relevant_date.parse_date().unwrap(), None);
+    assert_eq!(
+        relevant_date.parse_start_date().unwrap(),
+        Some(start.into())
+    );
+    assert_eq!(relevant_date.parse_end_date().unwrap(), Some(end.into()));
+    assert!(template.validate_dates().is_ok());
+}
+
+#[test]
+fn validate_dates_names_the_invalid_date() {
+    let mut template = template();
+    template.relevant_date("2024-11-01T17:30:00Z");
+    template.expiration_date("tomorrow");
+
+    assert!(matches!(
+        template.validate_dates(),
+        Err(Error::InvalidDate { key, value }) if key == "expirationDate" && value == "tomorrow"
+    ));
+    assert!(matches!(
+        template.parse_expiration_date(),
+        Err(Error::InvalidDate { key, .. }) if key == "expirationDate"
+    ));
+}
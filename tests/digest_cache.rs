@@ -0,0 +1,151 @@
+//! Cached digests of the assets of a `Pass` are invalidated when the assets change
+
+mod common;
+
+use openssl::sha::sha1;
+use std::fs::{self, File};
+use std::io::{Cursor, Read};
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+use wallet_pass::sign::SigningIdentity;
+use wallet_pass::Pass;
+
+use common::KeyType;
+
+/// Hex encoded SHA-1 digest like in the manifest
+fn digest(content: &[u8]) -> String {
+    sha1(content).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Digest of the asset in the manifest of a freshly exported archive
+fn manifest_digest(pass: &Pass, identity: &SigningIdentity, name: &str) -> Option<String> {
+    let archive = pass.export_to_vec(identity).unwrap();
+    let mut archive = zip::ZipArchive::new(Cursor::new(archive)).unwrap();
+    let mut manifest = String::new();
+    archive
+        .by_name("manifest.json")
+        .unwrap()
+        .read_to_string(&mut manifest)
+        .unwrap();
+    let manifest: serde_json::Value = serde_json::from_str(&manifest).unwrap();
+
+    manifest[name].as_str().map(str::to_owned)
+}
+
+fn pass_directory(name: &str) -> std::path::PathBuf {
+    let pass_path = common::temp_dir(name);
+    fs::write(
+        pass_path.join("pass.json"),
+        serde_json::to_vec(&common::template()).unwrap(),
+    )
+    .unwrap();
+    fs::write(pass_path.join("icon.png"), common::png(29, 29)).unwrap();
+
+    pass_path
+}
+
+/// Write the content and move the modification time forward, file systems with a coarse
+/// timestamp resolution would otherwise keep the old time
+fn rewrite(path: &Path, content: &[u8], modified: SystemTime) {
+    fs::write(path, content).unwrap();
+    File::options()
+        .write(true)
+        .open(path)
+        .unwrap()
+        .set_modified(modified)
+        .unwrap();
+}
+
+#[test]
+fn changed_size_is_hashed_again() {
+    let (identity, _) = common::identity(KeyType::Rsa);
+    let pass_path = pass_directory("digest-size");
+    let pass = Pass::from_path(&pass_path).unwrap();
+    assert_eq!(
+        manifest_digest(&pass, &identity, "icon.png"),
+        Some(digest(&common::png(29, 29)))
+    );
+
+    let icon = common::png(58, 58)
+        .into_iter()
+        .chain([0; 8])
+        .collect::<Vec<_>>();
+    let modified = fs::metadata(pass_path.join("icon.png"))
+        .unwrap()
+        .modified()
+        .unwrap();
+    rewrite(&pass_path.join("icon.png"), &icon, modified);
+
+    assert_eq!(
+        manifest_digest(&pass, &identity, "icon.png"),
+        Some(digest(&icon))
+    );
+    fs::remove_dir_all(pass_path).unwrap();
+}
+
+#[test]
+fn changed_modification_time_is_hashed_again() {
+    let (identity, _) = common::identity(KeyType::Rsa);
+    let pass_path = pass_directory("digest-mtime");
+    let pass = Pass::from_path(&pass_path).unwrap();
+    assert_eq!(
+        manifest_digest(&pass, &identity, "icon.png"),
+        Some(digest(&common::png(29, 29)))
+    );
+
+    // Same size, only the modification time tells the files apart
+    let icon = common::png(58, 58);
+    let modified = fs::metadata(pass_path.join("icon.png"))
+        .unwrap()
+        .modified()
+        .unwrap();
+    rewrite(
+        &pass_path.join("icon.png"),
+        &icon,
+        modified + Duration::from_secs(10),
+    );
+
+    assert_eq!(
+        manifest_digest(&pass, &identity, "icon.png"),
+        Some(digest(&icon))
+    );
+    fs::remove_dir_all(pass_path).unwrap();
+}
+
+#[test]
+fn new_and_removed_files() {
+    let (identity, _) = common::identity(KeyType::Rsa);
+    let pass_path = pass_directory("digest-new");
+    let pass = Pass::from_path(&pass_path).unwrap();
+    assert_eq!(manifest_digest(&pass, &identity, "logo.png"), None);
+
+    fs::write(pass_path.join("logo.png"), common::png(160, 50)).unwrap();
+    assert_eq!(
+        manifest_digest(&pass, &identity, "logo.png"),
+        Some(digest(&common::png(160, 50)))
+    );
+
+    fs::remove_file(pass_path.join("logo.png")).unwrap();
+    assert_eq!(manifest_digest(&pass, &identity, "logo.png"), None);
+    fs::remove_dir_all(pass_path).unwrap();
+}
+
+#[test]
+fn replaced_in_memory_asset_is_hashed_again() {
+    let (identity, _) = common::identity(KeyType::Rsa);
+    let pass_path = pass_directory("digest-memory");
+    let mut pass = Pass::from_path(&pass_path).unwrap();
+
+    pass.set_asset("logo.png", common::png(160, 50)).unwrap();
+    assert_eq!(
+        manifest_digest(&pass, &identity, "logo.png"),
+        Some(digest(&common::png(160, 50)))
+    );
+
+    pass.set_asset("logo.png", common::png(320, 100)).unwrap();
+    assert_eq!(
+        manifest_digest(&pass, &identity, "logo.png"),
+        Some(digest(&common::png(320, 100)))
+    );
+    fs::remove_dir_all(pass_path).unwrap();
+}
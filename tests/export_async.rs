@@ -0,0 +1,48 @@
+//! `Pass::export_async` produces the same archives as `Pass::export`
+#![cfg(feature = "async")]
+
+mod common;
+
+use std::fs;
+use std::sync::Arc;
+use std::time::{Duration, UNIX_EPOCH};
+use wallet_pass::Pass;
+
+use common::KeyType;
+
+#[test]
+fn async_export_matches_export() {
+    let (identity, _) = common::identity(KeyType::Rsa);
+    let identity = Arc::new(identity);
+
+    let pass_path = common::temp_dir("export-async");
+    fs::write(pass_path.join("icon.png"), common::png(29, 29)).unwrap();
+    fs::create_dir(pass_path.join("en.lproj")).unwrap();
+    fs::write(pass_path.join("en.lproj/pass.strings"), "\"a\" = \"b\";").unwrap();
+
+    // Linked directories are not followed, a loop must not break the export
+    let linked_path = common::temp_dir("export-async-linked");
+    fs::write(linked_path.join("logo.png"), common::png(160, 50)).unwrap();
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(&linked_path, pass_path.join("linked")).unwrap();
+        std::os::unix::fs::symlink(&pass_path, pass_path.join("loop")).unwrap();
+    }
+
+    let mut pass = Pass::from_template(&common::template(), &pass_path);
+    pass.options
+        .signing_time(UNIX_EPOCH + Duration::from_secs(1_730_485_800));
+
+    let archive = pass.export_to_vec(identity.as_ref()).unwrap();
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let async_archive = runtime
+        .block_on(pass.export_async(identity, Vec::new()))
+        .unwrap();
+
+    assert_eq!(archive, async_archive);
+
+    fs::remove_dir_all(pass_path).unwrap();
+    fs::remove_dir_all(linked_path).unwrap();
+}
@@ -0,0 +1,189 @@
+//! Selecting the files of a pass with `FileFilter` and rejecting files Apple Wallet does not accept
+
+mod common;
+
+use std::io::{Cursor, Read};
+use wallet_pass::sign::{self, FileFilter, SignOptions};
+use wallet_pass::{Error, Pass};
+
+use common::KeyType;
+
+/// Sign the files and return the names of the files of the archive, without directories
+fn sign_files(files: &[&str], options: &SignOptions) -> Result<Vec<String>, Error> {
+    let (identity, _) = common::identity(KeyType::Rsa);
+    let files = files
+        .iter()
+        .map(|name| (name.to_string(), common::png(29, 29)));
+
+    let archive = sign::sign_files(
+        files,
+        Some(&common::template()),
+        &identity,
+        Cursor::new(Vec::new()),
+        options,
+    )?;
+    let mut archive = zip::ZipArchive::new(archive).unwrap();
+    let mut names = archive
+        .file_names()
+        .filter(|name| !name.ends_with('/'))
+        .map(str::to_owned)
+        .collect::<Vec<_>>();
+    names.sort();
+
+    // The archive is complete
+    archive
+        .by_name("manifest.json")
+        .unwrap()
+        .read_to_end(&mut Vec::new())
+        .unwrap();
+
+    Ok(names)
+}
+
+#[test]
+fn default_excludes() {
+    let filter = FileFilter::new();
+
+    assert!(filter.is_included("icon.png"));
+    assert!(filter.is_included("de.lproj/logo.png"));
+    assert!(!filter.is_included(".DS_Store"));
+    assert!(!filter.is_included("de.lproj/.DS_Store"));
+    assert!(!filter.is_included(".git/HEAD"));
+    assert!(!filter.is_included("icon.png~"));
+    assert!(!filter.is_included("README.md"));
+    assert!(FileFilter::empty().is_included(".DS_Store"));
+}
+
+#[test]
+fn include_and_exclude() {
+    let mut filter = FileFilter::empty();
+    filter.include("*.png").unwrap();
+    filter.include("de.lproj/*").unwrap();
+    filter.exclude("strip*.png").unwrap();
+
+    assert!(filter.is_included("pass.json"));
+    assert!(filter.is_included("icon.png"));
+    assert!(filter.is_included("de.lproj/pass.strings"));
+    assert!(!filter.is_included("en.lproj/pass.strings"));
+    assert!(!filter.is_included("strip@2x.png"));
+    assert!(!filter.is_included("de.lproj/strip.png"));
+}
+
+#[test]
+fn patterns_with_separator_match_the_full_path() {
+    let mut filter = FileFilter::empty();
+    filter.exclude("de.lproj/*.png").unwrap();
+
+    assert!(!filter.is_included("de.lproj/logo.png"));
+    assert!(filter.is_included("de.lproj/pass.strings"));
+    assert!(filter.is_included("logo.png"));
+    assert!(filter.is_included("en.lproj/logo.png"));
+}
+
+#[test]
+fn pass_json_is_always_included() {
+    let mut filter = FileFilter::empty();
+    filter.include("*.png").unwrap();
+    filter.exclude("*.json").unwrap();
+
+    assert!(filter.is_included("pass.json"));
+    assert!(!filter.is_included("personalization.json"));
+}
+
+#[test]
+fn invalid_pattern() {
+    let mut filter = FileFilter::new();
+
+    assert!(matches!(
+        filter.exclude("[*.png"),
+        Err(Error::InvalidPattern(_))
+    ));
+    assert!(matches!(
+        filter.include("***"),
+        Err(Error::InvalidPattern(_))
+    ));
+}
+
+#[test]
+fn filtered_files_are_not_signed() {
+    let mut filter = FileFilter::new();
+    filter.exclude("strip.png").unwrap();
+    let mut options = SignOptions::new();
+    options.file_filter(filter);
+
+    let names = sign_files(
+        &["icon.png", "strip.png", ".DS_Store", "de.lproj/.DS_Store"],
+        &options,
+    )
+    .unwrap();
+
+    assert_eq!(
+        names,
+        ["icon.png", "manifest.json", "pass.json", "signature"]
+    );
+}
+
+#[test]
+fn accepted_asset_names() {
+    let names = sign_files(
+        &[
+            "icon.png",
+            "personalization.json",
+            "de.lproj/pass.strings",
+            "de.lproj/logo@2x.png",
+        ],
+        &SignOptions::new(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        names,
+        [
+            "de.lproj/logo@2x.png",
+            "de.lproj/pass.strings",
+            "icon.png",
+            "manifest.json",
+            "pass.json",
+            "personalization.json",
+            "signature",
+        ]
+    );
+}
+
+#[test]
+fn rejected_asset_names() {
+    for name in [
+        "icon.jpg",
+        ".png",
+        "notes.txt",
+        "pass.strings",
+        "images/icon.png",
+        ".lproj/logo.png",
+        "de.lproj/notes.txt",
+        "de.lproj/images/logo.png",
+    ] {
+        let result = sign_files(&["icon.png", name], &SignOptions::new());
+
+        assert!(
+            matches!(&result, Err(Error::InvalidAsset(message)) if message.contains(name)),
+            "{}: {:?}",
+            name,
+            result
+        );
+    }
+}
+
+#[test]
+fn set_asset_rejects_invalid_names() {
+    let mut pass = Pass::from_template(&common::template(), common::temp_dir("set-asset-name"));
+
+    assert!(matches!(
+        pass.set_asset("icon.gif", Vec::new()),
+        Err(Error::InvalidAsset(_))
+    ));
+    assert!(matches!(
+        pass.set_asset("pass.json", Vec::new()),
+        Err(Error::InvalidAsset(_))
+    ));
+    assert!(pass.set_asset("de.lproj/pass.strings", Vec::new()).is_ok());
+}
@@ -0,0 +1,192 @@
+//! Image names, PNG headers and the checks of `validate_images`
+
+mod common;
+
+use wallet_pass::asset::{
+    png_dimensions, validate_images, ImageAsset, ImageKind, ImageScale, MAX_IMAGE_FILE_LEN,
+};
+use wallet_pass::template::{Details, Severity};
+use wallet_pass::{Error, Pass};
+
+use common::KeyType;
+
+/// Severity and path of the diagnostics of the images of a generic pass
+fn validate(assets: &[(&str, Vec<u8>)]) -> Vec<(Severity, String)> {
+    let assets = assets
+        .iter()
+        .map(|(name, content)| (*name, content.as_slice()));
+
+    validate_images(assets, &common::template())
+        .into_iter()
+        .map(|diagnostic| (diagnostic.severity, diagnostic.path))
+        .collect()
+}
+
+#[test]
+fn png_dimensions_from_header() {
+    assert_eq!(png_dimensions(&common::png(29, 58)).unwrap(), (29, 58));
+
+    let mut png = common::png(160, 50);
+    png.extend_from_slice(&[0; 100]);
+    assert_eq!(png_dimensions(&png).unwrap(), (160, 50));
+}
+
+#[test]
+fn png_dimensions_of_other_files() {
+    let png = common::png(29, 29);
+    let mut jpeg = png.clone();
+    jpeg[..4].copy_from_slice(b"\xff\xd8\xff\xe0");
+    let mut missing_header = png.clone();
+    missing_header[12..16].copy_from_slice(b"IDAT");
+
+    for content in [&b""[..], &png[..23], &jpeg, &missing_header] {
+        assert!(matches!(
+            png_dimensions(content),
+            Err(Error::InvalidAsset(_))
+        ));
+    }
+}
+
+#[test]
+fn image_names() {
+    let image = ImageAsset::from_name("de.lproj/logo@2x.png").unwrap();
+    assert_eq!(image.kind, ImageKind::Logo);
+    assert_eq!(image.scale, ImageScale::X2);
+    assert_eq!(image.localization.as_deref(), Some("de"));
+    assert_eq!(image.name(), "de.lproj/logo@2x.png");
+
+    assert_eq!(
+        ImageAsset::from_name("personalizationLogo@3x.png"),
+        Some(ImageAsset::new(
+            ImageKind::PersonalizationLogo,
+            ImageScale::X3
+        ))
+    );
+    for name in [
+        "logo.jpg",
+        "logo@4x.png",
+        "banner.png",
+        "images/logo.png",
+        "Logo.png",
+    ] {
+        assert_eq!(ImageAsset::from_name(name), None, "{}", name);
+    }
+}
+
+#[test]
+fn valid_images() {
+    assert_eq!(
+        validate(&[
+            ("icon.png", common::png(29, 29)),
+            ("icon@2x.png", common::png(58, 58)),
+            ("logo@3x.png", common::png(480, 150)),
+            ("de.lproj/thumbnail.png", common::png(90, 90)),
+            ("de.lproj/pass.strings", Vec::new()),
+        ]),
+        []
+    );
+}
+
+#[test]
+fn missing_icon() {
+    assert_eq!(
+        validate(&[("logo.png", common::png(160, 50))]),
+        [(Severity::Error, "icon.png".to_string())]
+    );
+
+    // Localized icons are only used for their language
+    assert_eq!(
+        validate(&[("de.lproj/icon.png", common::png(29, 29))]),
+        [(Severity::Error, "icon.png".to_string())]
+    );
+}
+
+#[test]
+fn unused_and_invalid_images() {
+    assert_eq!(
+        validate(&[
+            ("icon.png", common::png(29, 29)),
+            ("banner.png", common::png(29, 29)),
+            ("logo.png", b"GIF89a".to_vec()),
+        ]),
+        [
+            (Severity::Warning, "banner.png".to_string()),
+            (Severity::Error, "logo.png".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn images_larger_than_recommended() {
+    assert_eq!(
+        validate(&[
+            ("icon.png", common::png(38, 38)),
+            ("icon@2x.png", common::png(77, 76)),
+            ("logo@3x.png", common::png(480, 151)),
+            ("strip.png", common::png(375, 123)),
+            ("artwork.png", common::png(4000, 4000)),
+        ]),
+        [
+            (Severity::Warning, "icon@2x.png".to_string()),
+            (Severity::Warning, "logo@3x.png".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn recommended_size_depends_on_style() {
+    let strip = common::png(375, 144);
+    let assets = [
+        ("icon.png", &common::png(29, 29)[..]),
+        ("strip.png", &strip[..]),
+    ];
+
+    let generic = validate_images(assets, &common::template());
+    assert_eq!(generic.len(), 1);
+    assert_eq!(generic[0].path, "strip.png");
+    assert_eq!(
+        generic[0].message,
+        "image is 375x144 pixels, Apple recommends at most 375x123 pixels"
+    );
+
+    let mut coupon = common::template();
+    coupon.coupon(Details::new());
+    assert!(validate_images(assets, &coupon).is_empty());
+}
+
+#[test]
+fn large_image_files() {
+    let mut icon = common::png(29, 29);
+    icon.resize(MAX_IMAGE_FILE_LEN + 1, 0);
+
+    assert_eq!(
+        validate(&[("icon.png", icon)]),
+        [(Severity::Warning, "icon.png".to_string())]
+    );
+}
+
+#[test]
+fn export_rejects_pass_without_icon() {
+    let (identity, _) = common::identity(KeyType::Rsa);
+    let pass_path = common::temp_dir("images");
+    let mut pass = Pass::from_template(&common::template(), &pass_path);
+    pass.set_asset("logo.png", common::png(160, 50)).unwrap();
+    assert_eq!(pass.validate_images().unwrap()[0].path, "icon.png");
+
+    match pass.export_to_vec(&identity) {
+        Err(Error::Validation(diagnostics)) => {
+            assert_eq!(diagnostics.len(), 1);
+            assert_eq!(diagnostics[0].path, "icon.png");
+        }
+        result => panic!("unexpected result {:?}", result.map(|_| ())),
+    }
+
+    pass.options.skip_validation(true);
+    assert!(pass.export_to_vec(&identity).is_ok());
+
+    pass.options.skip_validation(false);
+    pass.set_asset("icon.png", common::png(29, 29)).unwrap();
+    assert!(pass.validate_images().unwrap().is_empty());
+    assert!(pass.export_to_vec(&identity).is_ok());
+    std::fs::remove_dir_all(pass_path).unwrap();
+}
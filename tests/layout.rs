@@ -0,0 +1,258 @@
+//! Field limits and truncated values reported by `lint_layout`
+
+mod common;
+
+use wallet_pass::template::{
+    Barcode, BarcodeFormat, BoardingPass, Details, EStyle, Field, LayoutImages, PassStyle,
+    Template, TransitType,
+};
+use wallet_pass::Pass;
+
+/// Fields with short values and the given row
+fn fields(prefix: &str, count: usize, row: Option<i32>) -> Vec<Field> {
+    (0..count)
+        .map(|index| {
+            let mut field = Field::new_string(&format!("{}{}", prefix, index), "Value");
+            field.row = row;
+            field
+        })
+        .collect()
+}
+
+/// Json paths of the diagnostics
+fn paths(diagnostics: Vec<wallet_pass::template::Diagnostic>) -> Vec<String> {
+    diagnostics
+        .into_iter()
+        .map(|diagnostic| diagnostic.path)
+        .collect()
+}
+
+fn lint(style: PassStyle, images: LayoutImages, square_barcode: bool) -> Vec<String> {
+    paths(style.lint_layout(&images, square_barcode))
+}
+
+#[test]
+fn header_and_primary_limits() {
+    let mut details = Details::new();
+    details.header_fields = Some(fields("header", 4, None));
+    details.primary_fields = Some(fields("primary", 2, None));
+
+    assert_eq!(
+        lint(PassStyle::Generic(details), LayoutImages::new(), false),
+        ["generic.headerFields[3]", "generic.primaryFields[1]"]
+    );
+}
+
+#[test]
+fn boarding_pass_limits() {
+    let mut boarding_pass = BoardingPass::new(TransitType::PkTransitTypeAir);
+    boarding_pass.primary_fields = Some(fields("primary", 3, None));
+    boarding_pass.secondary_fields = Some(fields("secondary", 5, None));
+    boarding_pass.auxiliary_fields = Some(fields("auxiliary", 6, None));
+
+    assert_eq!(
+        lint(
+            PassStyle::BoardingPass(boarding_pass),
+            LayoutImages::new(),
+            false
+        ),
+        [
+            "boardingPass.primaryFields[2]",
+            "boardingPass.auxiliaryFields[5]"
+        ]
+    );
+}
+
+#[test]
+fn combined_secondary_and_auxiliary_fields() {
+    let mut details = Details::new();
+    details.secondary_fields = Some(fields("secondary", 3, None));
+    details.auxiliary_fields = Some(fields("auxiliary", 2, None));
+
+    // Coupons and store cards have a single row
+    for style in [
+        PassStyle::Coupon(details.clone()),
+        PassStyle::StoreCard(details.clone()),
+    ] {
+        let key = style.key();
+        assert_eq!(
+            lint(style, LayoutImages::new(), false),
+            [format!("{}.auxiliaryFields[1]", key)]
+        );
+    }
+
+    // Event tickets only with a strip image
+    let strip = LayoutImages {
+        strip: true,
+        ..LayoutImages::new()
+    };
+    assert_eq!(
+        lint(PassStyle::EventTicket(details.clone()), strip, false),
+        ["eventTicket.auxiliaryFields[1]"]
+    );
+    assert!(lint(
+        PassStyle::EventTicket(details.clone()),
+        LayoutImages::new(),
+        false
+    )
+    .is_empty());
+
+    // Generic passes only with a square barcode
+    assert_eq!(
+        lint(
+            PassStyle::Generic(details.clone()),
+            LayoutImages::new(),
+            true
+        ),
+        ["generic.auxiliaryFields[1]"]
+    );
+    assert!(lint(PassStyle::Generic(details), LayoutImages::new(), false).is_empty());
+}
+
+#[test]
+fn auxiliary_field_rows() {
+    let mut details = Details::new();
+    let mut auxiliary_fields = fields("first", 5, Some(0));
+    auxiliary_fields.extend(fields("second", 4, Some(1)));
+    details.auxiliary_fields = Some(auxiliary_fields);
+
+    assert_eq!(
+        lint(PassStyle::EventTicket(details), LayoutImages::new(), false),
+        ["eventTicket.auxiliaryFields[4]"]
+    );
+}
+
+#[test]
+fn truncated_values() {
+    let mut details = Details::new();
+    details.add_primary_field(Field::new_string("fits", &"a".repeat(18)));
+    details.secondary_fields = Some(vec![
+        Field::new_string("fits", &"a".repeat(16)),
+        Field::new_string("truncated", &"a".repeat(17)),
+    ]);
+
+    assert_eq!(
+        lint(
+            PassStyle::Generic(details.clone()),
+            LayoutImages::new(),
+            false
+        ),
+        ["generic.secondaryFields[1].value"]
+    );
+
+    // A thumbnail leaves less room for primary fields
+    let thumbnail = LayoutImages {
+        thumbnail: true,
+        ..LayoutImages::new()
+    };
+    assert_eq!(
+        lint(PassStyle::Generic(details), thumbnail, false),
+        [
+            "generic.primaryFields[0].value",
+            "generic.secondaryFields[1].value"
+        ]
+    );
+}
+
+#[test]
+fn dates_are_not_truncated() {
+    let mut date = Field::new_string("date", "2024-11-01T18:30:00+01:00");
+    date.date_style = Some(EStyle::PkDateStyleShort);
+    let mut details = Details::new();
+    details.add_primary_field(date);
+
+    assert!(lint(PassStyle::Generic(details), LayoutImages::new(), false).is_empty());
+}
+
+#[test]
+fn ignored_images() {
+    let images = LayoutImages {
+        background: true,
+        strip: true,
+        thumbnail: true,
+    };
+    let diagnostics = PassStyle::Generic(Details::new()).lint_layout(&images, false);
+    let messages = diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.message.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        [
+            "strip.png is not displayed on this generic pass",
+            "background.png is not displayed on this generic pass",
+        ]
+    );
+
+    // Event tickets show either a strip or a background and thumbnail
+    assert_eq!(
+        PassStyle::EventTicket(Details::new())
+            .lint_layout(&images, false)
+            .len(),
+        2
+    );
+    assert!(
+        PassStyle::BoardingPass(BoardingPass::new(TransitType::PkTransitTypeAir))
+            .lint_layout(&LayoutImages::new(), false)
+            .is_empty()
+    );
+}
+
+#[test]
+fn layout_images_from_asset_names() {
+    assert_eq!(
+        LayoutImages::from_asset_names(["icon.png", "strip@2x.png", "de.lproj/thumbnail.png"]),
+        LayoutImages {
+            background: false,
+            strip: true,
+            thumbnail: true,
+        }
+    );
+    assert_eq!(
+        LayoutImages::from_asset_names(["background.jpg", "logo.png"]),
+        LayoutImages::new()
+    );
+}
+
+#[test]
+fn template_uses_first_barcode() {
+    let mut details = Details::new();
+    details.secondary_fields = Some(fields("secondary", 4, None));
+    details.auxiliary_fields = Some(fields("auxiliary", 1, None));
+    let mut template = Template::new("Test", "wallet-pass", "pass.com.example.test", "1");
+    template.generic(details);
+    assert!(template.lint_layout(&LayoutImages::new()).is_empty());
+
+    template.add_barcodes(Barcode::new(
+        BarcodeFormat::PkBarcodeFormatQr,
+        "1",
+        "iso-8859-1",
+    ));
+    assert_eq!(
+        paths(template.lint_layout(&LayoutImages::new())),
+        ["generic.auxiliaryFields[0]"]
+    );
+
+    let mut template = Template::new("Test", "wallet-pass", "pass.com.example.test", "1");
+    assert!(template.lint_layout(&LayoutImages::new()).is_empty());
+    template.coupon(Details::new());
+    assert!(template.lint_layout(&LayoutImages::new()).is_empty());
+}
+
+#[test]
+fn pass_detects_images_of_its_assets() {
+    let pass_path = common::temp_dir("layout");
+    let mut pass = Pass::from_template(&common::template(), &pass_path);
+    assert!(pass.lint_layout().unwrap().is_empty());
+
+    pass.set_asset("strip@2x.png", common::png(750, 288))
+        .unwrap();
+    assert_eq!(
+        pass.lint_layout().unwrap()[0].message,
+        "strip.png is not displayed on this generic pass"
+    );
+
+    pass.remove_asset("strip@2x.png");
+    assert!(pass.lint_layout().unwrap().is_empty());
+    std::fs::remove_dir_all(pass_path).unwrap();
+}
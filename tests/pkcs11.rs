@@ -0,0 +1,70 @@
+//! Signs passes with a private key held by SoftHSM
+//!
+//! See "Sign with a hardware security module" in the README for the setup. The test is skipped
+//! unless `PKCS11_MODULE` is set.
+#![cfg(feature = "pkcs11")]
+
+use openssl::x509::X509;
+use std::env;
+use std::fs;
+use std::io::Cursor;
+use wallet_pass::sign::{self, CertificateInfo, Pkcs11Signer, SignOptions};
+use wallet_pass::template::{Details, Template};
+
+fn read_certificate(variable: &str) -> X509 {
+    let path = env::var(variable).unwrap_or_else(|_| panic!("{} is not set", variable));
+    X509::from_pem(&fs::read(path).unwrap()).unwrap()
+}
+
+#[test]
+fn sign_with_softhsm() {
+    let module = match env::var("PKCS11_MODULE") {
+        Ok(module) => module,
+        Err(_) => {
+            eprintln!("PKCS11_MODULE is not set, skipping");
+            return;
+        }
+    };
+    let certificate = read_certificate("PKCS11_CERTIFICATE");
+    let wwdr_intermediate_certificate = read_certificate("PKCS11_WWDR");
+
+    let new_signer = || {
+        Pkcs11Signer::new(
+            &module,
+            "wallet-pass",
+            "1234",
+            "pass",
+            certificate.clone(),
+            wwdr_intermediate_certificate.clone(),
+        )
+        .unwrap()
+    };
+    // The second signer finds the module already initialized by the first one
+    let signers = [new_signer(), new_signer()];
+
+    let pass_type_identifier = CertificateInfo::from_certificate(&certificate)
+        .unwrap()
+        .pass_type_identifier
+        .unwrap();
+    let mut template = Template::new("Test", "wallet-pass", &pass_type_identifier, "1");
+    template.generic(Details::new());
+
+    for signer in &signers {
+        let archive = sign::sign_files(
+            vec![("icon.png".to_owned(), b"icon".to_vec())],
+            Some(&template),
+            signer,
+            Cursor::new(Vec::new()),
+            &SignOptions::new(),
+        )
+        .unwrap()
+        .into_inner();
+
+        let report = sign::verify(
+            Cursor::new(archive),
+            std::slice::from_ref(&wwdr_intermediate_certificate),
+        )
+        .unwrap();
+        assert!(report.is_valid(), "{:?}", report);
+    }
+}
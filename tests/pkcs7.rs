@@ -0,0 +1,217 @@
+//! Signatures created by `SigningIdentity`, with OpenSSL and with the DER encoder for pinned
+//! signing times
+
+mod common;
+
+use openssl::pkcs7::Pkcs7;
+use openssl::sha::sha256;
+use std::io::{Cursor, Read};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use wallet_pass::sign::{self, SignOptions, SigningIdentity};
+use wallet_pass::Error;
+
+use common::KeyType;
+
+/// Sign a pass with an icon, optionally at a pinned signing time
+fn try_sign(
+    identity: &SigningIdentity,
+    signing_time: Option<SystemTime>,
+) -> wallet_pass::Result<Vec<u8>> {
+    let mut options = SignOptions::new();
+    if let Some(signing_time) = signing_time {
+        options.signing_time(signing_time);
+    }
+
+    let writer = sign::sign_files(
+        vec![("icon.png".to_owned(), b"icon".to_vec())],
+        Some(&common::template()),
+        identity,
+        Cursor::new(Vec::new()),
+        &options,
+    )?;
+
+    Ok(writer.into_inner())
+}
+
+/// Sign a pass with an icon at a pinned signing time and return the archive
+fn sign(identity: &SigningIdentity, signing_time: SystemTime) -> Vec<u8> {
+    try_sign(identity, Some(signing_time)).unwrap()
+}
+
+/// Read a file of an archive
+fn file(archive: &[u8], name: &str) -> Vec<u8> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(archive)).unwrap();
+    let mut content = Vec::new();
+    archive
+        .by_name(name)
+        .unwrap()
+        .read_to_end(&mut content)
+        .unwrap();
+
+    content
+}
+
+/// Read the `signature` of an archive
+fn signature(archive: &[u8]) -> Vec<u8> {
+    file(archive, "signature")
+}
+
+/// DER encoded certificates embedded in a signature
+fn certificates(signature: &[u8]) -> Vec<Vec<u8>> {
+    let pkcs7 = Pkcs7::from_der(signature).unwrap();
+    let mut certificates = pkcs7
+        .signed()
+        .unwrap()
+        .certificates()
+        .unwrap()
+        .iter()
+        .map(|certificate| certificate.to_der().unwrap())
+        .collect::<Vec<_>>();
+    certificates.sort();
+
+    certificates
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+/// 2024-11-01T18:30:00Z
+fn signing_time() -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(1_730_485_800)
+}
+
+#[test]
+fn openssl_rsa_signature_verifies() {
+    let (identity, ca) = common::identity(KeyType::Rsa);
+
+    let archive = try_sign(&identity, None).unwrap();
+    let report = sign::verify(Cursor::new(&archive), &[ca]).unwrap();
+
+    assert!(report.is_valid(), "{:?}", report);
+}
+
+#[test]
+fn openssl_ec_signature_verifies() {
+    let (identity, ca) = common::identity(KeyType::Ec);
+
+    let archive = try_sign(&identity, None).unwrap();
+    let report = sign::verify(Cursor::new(&archive), &[ca]).unwrap();
+
+    assert!(report.is_valid(), "{:?}", report);
+}
+
+#[test]
+fn pinned_rsa_signature_verifies() {
+    let (identity, ca) = common::identity(KeyType::Rsa);
+
+    let archive = sign(&identity, signing_time());
+    let report = sign::verify(Cursor::new(&archive), &[ca]).unwrap();
+
+    assert!(report.is_valid(), "{:?}", report);
+}
+
+#[test]
+fn pinned_ec_signature_is_rejected() {
+    let (identity, _) = common::identity(KeyType::Ec);
+
+    let result = try_sign(&identity, Some(signing_time()));
+
+    assert!(matches!(result, Err(Error::Certificate(_))));
+}
+
+#[test]
+fn pinned_signature_matches_openssl_signature() {
+    let (identity, _) = common::identity(KeyType::Rsa);
+
+    let pinned = sign(&identity, signing_time());
+    let openssl = try_sign(&identity, None).unwrap();
+
+    // Both embed the pass type certificate and the wwdr intermediate certificate
+    assert_eq!(
+        certificates(&signature(&pinned)),
+        certificates(&signature(&openssl))
+    );
+
+    // The message digest attribute is the SHA-256 digest of the manifest
+    let digest = [&b"\x04\x20"[..], &sha256(&file(&pinned, "manifest.json"))].concat();
+    assert!(contains(&signature(&pinned), &digest));
+
+    // 2024-11-01T18:30:00Z as UTCTime
+    assert!(contains(&signature(&pinned), b"\x17\x0d241101183000Z"));
+}
+
+#[test]
+fn pinned_signing_time_is_reproducible() {
+    let (identity, _) = common::identity(KeyType::Rsa);
+
+    assert_eq!(
+        sign(&identity, signing_time()),
+        sign(&identity, signing_time())
+    );
+}
+
+#[test]
+fn signing_time_switches_to_generalized_time_in_2050() {
+    let (identity, ca) = common::identity(KeyType::Rsa);
+
+    // 2049-12-31T23:59:59Z is encoded as UTCTime
+    let archive = sign(&identity, UNIX_EPOCH + Duration::from_secs(2_524_607_999));
+    assert!(contains(&signature(&archive), b"\x17\x0d491231235959Z"));
+    let report = sign::verify(Cursor::new(&archive), std::slice::from_ref(&ca)).unwrap();
+    assert!(report.is_valid(), "{:?}", report);
+
+    // 2050-01-01T00:00:00Z is encoded as GeneralizedTime
+    let archive = sign(&identity, UNIX_EPOCH + Duration::from_secs(2_524_608_000));
+    assert!(contains(&signature(&archive), b"\x18\x0f20500101000000Z"));
+    let report = sign::verify(Cursor::new(&archive), &[ca]).unwrap();
+    assert!(report.is_valid(), "{:?}", report);
+}
+
+#[test]
+fn writer_without_seek_gets_same_archive() {
+    let (identity, _) = common::identity(KeyType::Rsa);
+    let mut options = SignOptions::new();
+    options.signing_time(signing_time());
+
+    let archive = sign::sign_files_to_writer(
+        vec![("icon.png".to_owned(), b"icon".to_vec())],
+        Some(&common::template()),
+        &identity,
+        Vec::new(),
+        &options,
+    )
+    .unwrap();
+
+    assert_eq!(archive, sign(&identity, signing_time()));
+}
+
+#[test]
+fn signing_time_outside_certificate_validity_is_rejected() {
+    let (identity, _) = common::identity(KeyType::Rsa);
+    let files = || vec![("icon.png".to_owned(), b"icon".to_vec())];
+    let mut options = SignOptions::new();
+
+    // 2019-01-01T00:00:00Z, before the certificates are valid
+    options.signing_time(UNIX_EPOCH + Duration::from_secs(1_546_300_800));
+    let result = sign::sign_files(
+        files(),
+        Some(&common::template()),
+        &identity,
+        Cursor::new(Vec::new()),
+        &options,
+    );
+    assert!(matches!(result, Err(Error::CertificateExpired(_))));
+
+    options.allow_expired_certificates(true);
+    let result = sign::sign_files(
+        files(),
+        Some(&common::template()),
+        &identity,
+        Cursor::new(Vec::new()),
+        &options,
+    );
+    assert!(result.is_ok());
+}
@@ -0,0 +1,75 @@
+//! Opening signed pass archives with `Pass::from_pkpass`
+
+mod common;
+
+use std::fs;
+use std::io::Cursor;
+use std::time::{Duration, UNIX_EPOCH};
+use wallet_pass::{Error, Pass};
+
+use common::KeyType;
+
+#[test]
+fn from_pkpass_round_trip() {
+    let (identity, _) = common::identity(KeyType::Rsa);
+    let pass_path = common::temp_dir("pkpass");
+    let mut template = common::template();
+    template.team_identifier = Some("ABCDE12345".into());
+    fs::write(
+        pass_path.join("pass.json"),
+        serde_json::to_vec(&template).unwrap(),
+    )
+    .unwrap();
+    fs::write(pass_path.join("icon.png"), common::png(29, 29)).unwrap();
+    fs::create_dir(pass_path.join("de.lproj")).unwrap();
+    fs::write(pass_path.join("de.lproj/logo.png"), common::png(160, 50)).unwrap();
+
+    let mut pass = Pass::from_path(&pass_path).unwrap();
+    pass.options
+        .signing_time(UNIX_EPOCH + Duration::from_secs(1_730_485_800));
+    let archive = pass.export_to_vec(&identity).unwrap();
+
+    let mut opened = Pass::from_pkpass(Cursor::new(&archive)).unwrap();
+    assert_eq!(opened.assets().unwrap(), pass.assets().unwrap());
+    assert!(!opened.assets().unwrap().contains_key("manifest.json"));
+    assert!(!opened.assets().unwrap().contains_key("signature"));
+    assert_eq!(
+        serde_json::to_value(&opened.template).unwrap(),
+        serde_json::to_value(&pass.template).unwrap()
+    );
+
+    // Signing the opened pass again gives the same archive
+    opened.options = pass.options.clone();
+    assert_eq!(opened.export_to_vec(&identity).unwrap(), archive);
+
+    fs::remove_dir_all(pass_path).unwrap();
+}
+
+#[test]
+fn from_pkpass_requires_pass_json() {
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    zip.start_file("icon.png", zip::write::FileOptions::default())
+        .unwrap();
+    let archive = zip.finish().unwrap().into_inner();
+
+    let result = Pass::from_pkpass(Cursor::new(archive));
+
+    assert!(matches!(result, Err(Error::MissingPassJson)));
+}
+
+#[test]
+fn from_pkpass_rejects_other_files() {
+    let result = Pass::from_pkpass(Cursor::new(b"not a zip archive".to_vec()));
+
+    assert!(matches!(result, Err(Error::Zip(_))));
+}
+
+#[test]
+fn from_path_requires_pass_json() {
+    let pass_path = common::temp_dir("pkpass-missing");
+
+    let result = Pass::from_path(&pass_path);
+
+    assert!(matches!(result, Err(Error::MissingPassJson)));
+    fs::remove_dir_all(pass_path).unwrap();
+}
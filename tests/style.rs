@@ -0,0 +1,112 @@
+//! A pass has exactly one style
+
+use wallet_pass::template::{BoardingPass, Details, Field, PassStyle, Template, TransitType};
+
+fn template() -> Template {
+    Template::new("Test", "wallet-pass", "pass.com.example.test", "1")
+}
+
+fn details(key: &str) -> Details {
+    let mut details = Details::new();
+    details.add_primary_field(Field::new_string(key, "Value"));
+
+    details
+}
+
+/// Json keys of the styles set in the `pass.json`
+fn style_keys(template: &Template) -> Vec<String> {
+    let keys = [
+        "boardingPass",
+        "coupon",
+        "eventTicket",
+        "generic",
+        "storeCard",
+    ];
+    let value = serde_json::to_value(template).unwrap();
+
+    keys.iter()
+        .filter(|key| value.get(**key).is_some())
+        .map(|key| key.to_string())
+        .collect()
+}
+
+#[test]
+fn set_style_replaces_other_styles() {
+    let mut template = template();
+    assert!(template.style().is_none());
+
+    template.set_style(PassStyle::Generic(details("generic")));
+    assert_eq!(style_keys(&template), ["generic"]);
+
+    template.set_style(PassStyle::BoardingPass(BoardingPass::new(
+        TransitType::PkTransitTypeAir,
+    )));
+    assert_eq!(style_keys(&template), ["boardingPass"]);
+    assert!(template.generic.is_none());
+
+    template.set_style(PassStyle::StoreCard(details("store")));
+    assert_eq!(style_keys(&template), ["storeCard"]);
+    assert!(template.boarding_pass.is_none());
+}
+
+#[test]
+fn style_setters_replace_other_styles() {
+    let mut template = template();
+    template.coupon(details("coupon"));
+    template.event_ticket(details("event"));
+    assert_eq!(style_keys(&template), ["eventTicket"]);
+
+    template.store_card(details("store"));
+    template.generic(details("generic"));
+    assert_eq!(style_keys(&template), ["generic"]);
+
+    template.boarding_pass(BoardingPass::new(TransitType::PkTransitTypeTrain));
+    assert_eq!(style_keys(&template), ["boardingPass"]);
+}
+
+#[test]
+fn style_returns_the_set_style() {
+    let mut template = template();
+    template.event_ticket(details("event"));
+
+    match template.style() {
+        Some(PassStyle::EventTicket(details)) => {
+            assert_eq!(details.primary_fields.unwrap()[0].key, "event")
+        }
+        style => panic!("unexpected style {:?}", style),
+    }
+    assert_eq!(template.style().unwrap().key(), "eventTicket");
+}
+
+#[test]
+fn take_style_removes_every_style() {
+    // Passes loaded from json may have more than one style
+    let mut template: Template = serde_json::from_value(serde_json::json!({
+        "description": "Test",
+        "formatVersion": 1,
+        "organizationName": "wallet-pass",
+        "passTypeIdentifier": "pass.com.example.test",
+        "serialNumber": "1",
+        "coupon": {},
+        "storeCard": {},
+    }))
+    .unwrap();
+    assert_eq!(template.style().unwrap().key(), "coupon");
+
+    let style = template.take_style();
+
+    assert_eq!(style.unwrap().key(), "coupon");
+    assert!(style_keys(&template).is_empty());
+    assert!(template.style().is_none());
+}
+
+#[test]
+fn set_style_fixes_passes_with_multiple_styles() {
+    let mut template = template();
+    template.coupon = Some(details("coupon"));
+    template.generic = Some(details("generic"));
+
+    template.set_style(PassStyle::Coupon(details("coupon")));
+
+    assert_eq!(style_keys(&template), ["coupon"]);
+}
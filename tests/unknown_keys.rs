@@ -0,0 +1,131 @@
+//! Keys of the pass.json that are not known to this crate
+#![recursion_limit = "256"]
+
+use serde_json::json;
+use wallet_pass::template::Template;
+use wallet_pass::Error;
+
+/// pass.json with every top-level key known to this crate
+fn known_keys() -> serde_json::Value {
+    json!({
+        "accessibilityURL": "https://example.com/accessibility",
+        "addOnURL": "https://example.com/add-on",
+        "appLaunchURL": "example://pass",
+        "associatedStoreIdentifiers": [1.0],
+        "authenticationToken": "0123456789abcdef",
+        "auxiliaryStoreIdentifiers": [2.0],
+        "backgroundColor": "rgb(0, 0, 0)",
+        "bagPolicyURL": "https://example.com/bags",
+        "barcode": {"format": "PKBarcodeFormatQR", "message": "1", "messageEncoding": "iso-8859-1"},
+        "barcodes": [{"format": "PKBarcodeFormatQR", "message": "1", "messageEncoding": "iso-8859-1"}],
+        "beacons": [{"proximityUUID": "E2C56DB5-DFFB-48D2-B060-D0F5A71096E0"}],
+        "boardingPass": {"transitType": "PKTransitTypeAir"},
+        "contactVenueEmail": "venue@example.com",
+        "contactVenuePhoneNumber": "+1 555 0100",
+        "contactVenueWebsite": "https://example.com/venue",
+        "coupon": {},
+        "description": "Test",
+        "directionsInformationURL": "https://example.com/directions",
+        "eventLogoText": "Event",
+        "eventTicket": {},
+        "expirationDate": "2030-01-01T00:00:00Z",
+        "footerBackgroundColor": "rgb(0, 0, 0)",
+        "foregroundColor": "rgb(255, 255, 255)",
+        "formatVersion": 1,
+        "generic": {},
+        "groupingIdentifier": "group",
+        "labelColor": "rgb(255, 255, 255)",
+        "locations": [{"latitude": 52.5, "longitude": 13.4}],
+        "logoText": "Logo",
+        "maxDistance": 100.0,
+        "merchandiseURL": "https://example.com/merchandise",
+        "nfc": {"message": "nfc"},
+        "orderFoodURL": "https://example.com/food",
+        "organizationName": "wallet-pass",
+        "parkingInformationURL": "https://example.com/parking",
+        "passTypeIdentifier": "pass.com.example.test",
+        "preferredStyleSchemes": ["posterEventTicket", "eventTicket"],
+        "purchaseParkingURL": "https://example.com/buy-parking",
+        "relevantDate": "2030-01-01T00:00:00Z",
+        "relevantDates": [{"date": "2030-01-01T00:00:00Z"}],
+        "sellURL": "https://example.com/sell",
+        "semantics": {},
+        "serialNumber": "1",
+        "storeCard": {},
+        "suppressHeaderDarkening": true,
+        "suppressStripShine": true,
+        "teamIdentifier": "ABCDE12345",
+        "transferURL": "https://example.com/transfer",
+        "transitInformationURL": "https://example.com/transit",
+        "useAutomaticColors": true,
+        "userInfo": {},
+        "voided": false,
+        "webServiceURL": "https://example.com/passes",
+    })
+}
+
+#[test]
+fn known_keys_are_not_extra() {
+    let template: Template = serde_json::from_value(known_keys()).unwrap();
+
+    assert!(template.unknown_keys().is_empty());
+    assert!(template.deny_unknown_keys().is_ok());
+}
+
+#[test]
+fn add_extra_rejects_known_keys() {
+    let mut template = Template::new("Test", "wallet-pass", "pass.com.example.test", "1");
+
+    for key in known_keys().as_object().unwrap().keys() {
+        let result = template.add_extra(key, json!(1));
+        assert!(
+            matches!(&result, Err(Error::KnownKey(known)) if known == key),
+            "{}",
+            key
+        );
+    }
+    assert!(template.extra.is_empty());
+}
+
+#[test]
+fn add_extra_adds_unknown_keys() {
+    let mut template = Template::new("Test", "wallet-pass", "pass.com.example.test", "1");
+
+    template.add_extra("futureKey", json!({"a": 1})).unwrap();
+
+    let json = serde_json::to_value(&template).unwrap();
+    assert_eq!(json["futureKey"], json!({"a": 1}));
+    assert_eq!(template.unknown_keys(), vec!["futureKey"]);
+}
+
+#[test]
+fn unknown_keys_round_trip() {
+    let mut json = known_keys();
+    json["futureKey"] = json!("future");
+    json["eventTicket"] = json!({
+        "primaryFields": [{"key": "event", "value": "Concert", "futureFieldKey": [1, 2]}],
+        "futureDetailsKey": true,
+    });
+    json["semantics"] = json!({
+        "seats": [{"seatNumber": "1", "futureSeatKey": {"a": null}}],
+    });
+    json["barcodes"][0]["futureBarcodeKey"] = json!(1.5);
+
+    let template: Template = serde_json::from_value(json.clone()).unwrap();
+
+    assert_eq!(serde_json::to_value(&template).unwrap(), json);
+    assert_eq!(
+        template.unknown_keys(),
+        vec![
+            "futureKey",
+            "barcodes[0].futureBarcodeKey",
+            "eventTicket.futureDetailsKey",
+            "eventTicket.primaryFields[0].futureFieldKey",
+            "semantics.seats[0].futureSeatKey",
+        ]
+    );
+    assert!(matches!(
+        template.deny_unknown_keys(),
+        Err(Error::UnknownKeys(keys)) if keys.len() == 5
+    ));
+}
@@ -0,0 +1,461 @@
+//! Rules of Apple Wallet checked by `Template::validate`
+
+#![recursion_limit = "256"]
+
+use serde_json::{json, Value};
+use wallet_pass::template::{Severity, Template};
+
+use Severity::{Error, Warning};
+
+/// Valid generic pass with the given top-level keys replaced, `null` removes a key
+fn pass(keys: Value) -> Value {
+    let mut pass = json!({
+        "description": "Test",
+        "formatVersion": 1,
+        "organizationName": "wallet-pass",
+        "passTypeIdentifier": "pass.com.example.test",
+        "serialNumber": "1",
+        "teamIdentifier": "ABCDE12345",
+        "generic": {},
+    });
+    for (key, value) in keys.as_object().unwrap() {
+        if value.is_null() {
+            pass.as_object_mut().unwrap().remove(key);
+        } else {
+            pass[key] = value.clone();
+        }
+    }
+
+    pass
+}
+
+/// Severity and json path of the diagnostics of the pass
+fn validate(pass: Value) -> Vec<(Severity, String)> {
+    let template: Template = serde_json::from_value(pass).unwrap();
+
+    template
+        .validate()
+        .into_iter()
+        .map(|diagnostic| (diagnostic.severity, diagnostic.path))
+        .collect()
+}
+
+/// Diagnostics of a generic pass with the given fields as secondary fields
+fn validate_fields(fields: Value) -> Vec<(Severity, String)> {
+    validate(pass(json!({ "generic": { "secondaryFields": fields } })))
+}
+
+fn diagnostics(expected: &[(Severity, &str)]) -> Vec<(Severity, String)> {
+    expected
+        .iter()
+        .map(|(severity, path)| (*severity, path.to_string()))
+        .collect()
+}
+
+#[test]
+fn valid_pass() {
+    assert_eq!(validate(pass(json!({}))), []);
+}
+
+#[test]
+fn required_keys() {
+    let pass = pass(json!({
+        "description": "",
+        "organizationName": "",
+        "passTypeIdentifier": "",
+        "serialNumber": "",
+        "teamIdentifier": null,
+        "formatVersion": null,
+    }));
+
+    assert_eq!(
+        validate(pass),
+        diagnostics(&[
+            (Error, "description"),
+            (Error, "organizationName"),
+            (Error, "passTypeIdentifier"),
+            (Error, "serialNumber"),
+            (Error, "formatVersion"),
+            (Error, "teamIdentifier"),
+        ])
+    );
+    assert_eq!(
+        validate(self::pass(json!({ "teamIdentifier": "" }))),
+        diagnostics(&[(Error, "teamIdentifier")])
+    );
+}
+
+#[test]
+fn pass_type_identifier_prefix() {
+    assert_eq!(
+        validate(pass(json!({ "passTypeIdentifier": "com.example.test" }))),
+        diagnostics(&[(Warning, "passTypeIdentifier")])
+    );
+}
+
+#[test]
+fn format_version() {
+    assert_eq!(
+        validate(pass(json!({ "formatVersion": 2 }))),
+        diagnostics(&[(Error, "formatVersion")])
+    );
+}
+
+#[test]
+fn exactly_one_style() {
+    assert_eq!(
+        validate(pass(json!({ "generic": null }))),
+        diagnostics(&[(Error, "")])
+    );
+    assert_eq!(
+        validate(pass(json!({ "coupon": {}, "storeCard": {} }))),
+        diagnostics(&[(Error, "generic"), (Error, "storeCard")])
+    );
+}
+
+#[test]
+fn transit_type_outside_boarding_passes() {
+    assert_eq!(
+        validate(pass(
+            json!({ "generic": { "transitType": "PKTransitTypeAir" } })
+        )),
+        diagnostics(&[(Warning, "generic.transitType")])
+    );
+}
+
+#[test]
+fn additional_info_fields_outside_event_tickets() {
+    let fields = json!([{ "key": "info", "value": "Info" }]);
+
+    assert_eq!(
+        validate(pass(
+            json!({ "generic": { "additionalInfoFields": fields } })
+        )),
+        diagnostics(&[(Warning, "generic.additionalInfoFields")])
+    );
+    assert_eq!(
+        validate(pass(json!({
+            "generic": null,
+            "eventTicket": { "additionalInfoFields": fields },
+        }))),
+        []
+    );
+}
+
+#[test]
+fn poster_scheme_requires_event_ticket() {
+    assert_eq!(
+        validate(pass(
+            json!({ "preferredStyleSchemes": ["posterEventTicket"] })
+        )),
+        diagnostics(&[(Error, "preferredStyleSchemes")])
+    );
+    assert_eq!(
+        validate(pass(json!({
+            "generic": null,
+            "eventTicket": {},
+            "preferredStyleSchemes": ["posterEventTicket", "eventTicket"],
+        }))),
+        []
+    );
+}
+
+#[test]
+fn grouping_identifier() {
+    assert_eq!(
+        validate(pass(json!({ "groupingIdentifier": "group" }))),
+        diagnostics(&[(Warning, "groupingIdentifier")])
+    );
+    assert_eq!(
+        validate(pass(json!({
+            "generic": null,
+            "boardingPass": { "transitType": "PKTransitTypeAir" },
+            "groupingIdentifier": "group",
+        }))),
+        []
+    );
+}
+
+#[test]
+fn field_keys() {
+    let pass = pass(json!({
+        "generic": {
+            "primaryFields": [{ "key": "name", "value": "Name" }],
+            "secondaryFields": [
+                { "key": "", "value": "Empty" },
+                { "key": "name", "value": "Duplicate" },
+            ],
+            "backFields": [{ "key": "name", "value": "Duplicate" }],
+        },
+    }));
+
+    assert_eq!(
+        validate(pass),
+        diagnostics(&[
+            (Error, "generic.secondaryFields[0].key"),
+            (Error, "generic.secondaryFields[1].key"),
+            (Error, "generic.backFields[0].key"),
+        ])
+    );
+}
+
+#[test]
+fn text_alignment() {
+    let pass = pass(json!({
+        "generic": {
+            "primaryFields": [
+                { "key": "primary", "value": "", "textAlignment": "PKTextAlignmentLeft" },
+            ],
+            "secondaryFields": [
+                { "key": "secondary", "value": "", "textAlignment": "PKTextAlignmentLeft" },
+            ],
+            "backFields": [
+                { "key": "back", "value": "", "textAlignment": "PKTextAlignmentLeft" },
+            ],
+        },
+    }));
+
+    assert_eq!(
+        validate(pass),
+        diagnostics(&[
+            (Warning, "generic.primaryFields[0].textAlignment"),
+            (Warning, "generic.backFields[0].textAlignment"),
+        ])
+    );
+}
+
+#[test]
+fn number_formats() {
+    let fields = json!([
+        { "key": "price", "value": 10.5, "currencyCode": "EUR" },
+        { "key": "share", "value": 0.5, "numberStyle": "PKNumberStylePercent" },
+        { "key": "text_price", "value": "10.50", "currencyCode": "EUR" },
+        { "key": "text_share", "value": "50", "numberStyle": "PKNumberStylePercent" },
+        {
+            "key": "both",
+            "value": 10.5,
+            "currencyCode": "EUR",
+            "numberStyle": "PKNumberStyleDecimal",
+        },
+    ]);
+
+    assert_eq!(
+        validate_fields(fields),
+        diagnostics(&[
+            (Error, "generic.secondaryFields[2].currencyCode"),
+            (Error, "generic.secondaryFields[3].numberStyle"),
+            (Warning, "generic.secondaryFields[4].numberStyle"),
+        ])
+    );
+}
+
+#[test]
+fn date_style_requires_date_value() {
+    let fields = json!([
+        { "key": "date", "value": "2024-11-01T18:30:00+01:00", "dateStyle": "PKDateStyleShort" },
+        { "key": "number", "value": 1730482200, "dateStyle": "PKDateStyleShort" },
+        { "key": "time", "value": 1730482200, "timeStyle": "PKDateStyleShort" },
+    ]);
+
+    assert_eq!(
+        validate_fields(fields),
+        diagnostics(&[
+            (Error, "generic.secondaryFields[1].value"),
+            (Error, "generic.secondaryFields[2].value"),
+        ])
+    );
+}
+
+#[test]
+fn row() {
+    let pass = pass(json!({
+        "generic": {
+            "secondaryFields": [{ "key": "secondary", "value": "", "row": 0 }],
+            "auxiliaryFields": [
+                { "key": "first", "value": "", "row": 0 },
+                { "key": "second", "value": "", "row": 1 },
+                { "key": "third", "value": "", "row": 2 },
+            ],
+        },
+    }));
+
+    assert_eq!(
+        validate(pass),
+        diagnostics(&[
+            (Warning, "generic.secondaryFields[0].row"),
+            (Error, "generic.auxiliaryFields[2].row"),
+        ])
+    );
+}
+
+#[test]
+fn barcodes() {
+    let pass = pass(json!({
+        "barcode": {
+            "format": "PKBarcodeFormatCode128",
+            "message": "",
+            "messageEncoding": "iso-8859-1",
+        },
+        "barcodes": [
+            { "format": "PKBarcodeFormatCode128", "message": "1", "messageEncoding": "" },
+            { "format": "PKBarcodeFormatQR", "message": "1", "messageEncoding": "iso-8859-1" },
+        ],
+    }));
+
+    assert_eq!(
+        validate(pass),
+        diagnostics(&[
+            (Warning, "barcode"),
+            (Error, "barcode.format"),
+            (Error, "barcode.message"),
+            (Error, "barcodes[0].messageEncoding"),
+        ])
+    );
+}
+
+#[test]
+fn beacons() {
+    let beacon = json!({ "proximityUUID": "E2C56DB5-DFFB-48D2-B060-D0F5A71096E0" });
+    let pass = pass(json!({
+        "beacons": [
+            { "proximityUUID": "E2C56DB5-DFFB-48D2-B060-D0F5A71096E0", "major": 0, "minor": 65535 },
+            { "proximityUUID": "E2C56DB5DFFB48D2B060D0F5A71096E0" },
+            { "proximityUUID": "G2C56DB5-DFFB-48D2-B060-D0F5A71096E0" },
+            { "proximityUUID": "E2C56DB5-DFFB-48D2-B060-D0F5A71096E0", "major": -1, "minor": 65536 },
+            beacon, beacon, beacon, beacon, beacon, beacon, beacon,
+        ],
+    }));
+
+    assert_eq!(
+        validate(pass),
+        diagnostics(&[
+            (Warning, "beacons"),
+            (Error, "beacons[1].proximityUUID"),
+            (Error, "beacons[2].proximityUUID"),
+            (Error, "beacons[3].major"),
+            (Error, "beacons[3].minor"),
+        ])
+    );
+}
+
+#[test]
+fn locations() {
+    let location = json!({ "latitude": 52.52, "longitude": 13.40 });
+    let pass = pass(json!({
+        "locations": [
+            { "latitude": -90.0, "longitude": 180.0 },
+            { "latitude": 90.5, "longitude": 13.40 },
+            { "latitude": 52.52, "longitude": -180.5 },
+            location, location, location, location, location, location, location, location,
+        ],
+        "maxDistance": -1.0,
+    }));
+
+    assert_eq!(
+        validate(pass),
+        diagnostics(&[
+            (Warning, "locations"),
+            (Error, "locations[1].latitude"),
+            (Error, "locations[2].longitude"),
+            (Error, "maxDistance"),
+        ])
+    );
+}
+
+#[test]
+fn relevant_dates() {
+    let pass = pass(json!({
+        "relevantDates": [
+            { "date": "2024-11-01T18:30:00+01:00" },
+            {
+                "startDate": "2024-11-01T18:30:00+01:00",
+                "endDate": "2024-11-01T22:00:00+01:00",
+            },
+            {},
+            { "date": "2024-11-01T18:30:00+01:00", "startDate": "2024-11-01T18:30:00+01:00" },
+            { "date": "2024-11-01T18:30:00+01:00", "endDate": "2024-11-01T22:00:00+01:00" },
+        ],
+    }));
+
+    assert_eq!(
+        validate(pass),
+        diagnostics(&[
+            (Error, "relevantDates[2]"),
+            (Error, "relevantDates[3]"),
+            (Error, "relevantDates[4].endDate"),
+        ])
+    );
+}
+
+#[test]
+fn web_service() {
+    assert_eq!(
+        validate(pass(
+            json!({ "webServiceURL": "https://example.com/passes" })
+        )),
+        diagnostics(&[(Error, "authenticationToken")])
+    );
+    assert_eq!(
+        validate(pass(json!({ "authenticationToken": "0123456789abcdef" }))),
+        diagnostics(&[(Warning, "authenticationToken")])
+    );
+    assert_eq!(
+        validate(pass(json!({
+            "webServiceURL": "http://example.com/passes",
+            "authenticationToken": "0123456789abcde",
+        }))),
+        diagnostics(&[(Error, "authenticationToken"), (Warning, "webServiceURL")])
+    );
+    assert_eq!(
+        validate(pass(json!({
+            "webServiceURL": "https://example.com/passes",
+            "authenticationToken": "0123456789abcdef",
+        }))),
+        []
+    );
+}
+
+#[test]
+fn nfc_message_length() {
+    assert_eq!(
+        validate(pass(json!({ "nfc": { "message": "a".repeat(64) } }))),
+        []
+    );
+    assert_eq!(
+        validate(pass(json!({ "nfc": { "message": "a".repeat(65) } }))),
+        diagnostics(&[(Warning, "nfc.message")])
+    );
+}
+
+#[test]
+fn store_identifiers() {
+    assert_eq!(
+        validate(pass(
+            json!({ "associatedStoreIdentifiers": [1.0, 1.5, -2.0, 284971959.0] })
+        )),
+        diagnostics(&[
+            (Error, "associatedStoreIdentifiers[1]"),
+            (Error, "associatedStoreIdentifiers[2]"),
+        ])
+    );
+}
+
+#[test]
+fn diagnostics_display() {
+    let template: Template =
+        serde_json::from_value(pass(json!({ "formatVersion": 2, "generic": null }))).unwrap();
+    let messages = template
+        .validate()
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        messages,
+        [
+            "error at formatVersion: format version has to be 1",
+            "error: pass has no style, set one of boardingPass, coupon, eventTicket, generic or \
+             storeCard",
+        ]
+    );
+}
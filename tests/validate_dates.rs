@@ -0,0 +1,100 @@
+//! Dates reported by `Template::validate`
+
+use wallet_pass::template::{Details, EStyle, Field, RelevantDate, Semantics, Severity, Template};
+
+/// Json paths of the invalid dates reported by `Template::validate`
+fn invalid_dates(template: &Template) -> Vec<String> {
+    template
+        .validate()
+        .into_iter()
+        .filter(|diagnostic| diagnostic.message.contains("ISO 8601"))
+        .map(|diagnostic| {
+            assert_eq!(diagnostic.severity, Severity::Error);
+            diagnostic.path
+        })
+        .collect()
+}
+
+fn template() -> Template {
+    let mut template = Template::new("Test", "wallet-pass", "pass.com.example.test", "1");
+    template.generic(Details::new());
+
+    template
+}
+
+#[test]
+fn accepts_iso_8601_dates() {
+    let valid = [
+        "2024-11-01T18:30:00+01:00",
+        "2024-11-01T18:30:00Z",
+        "2024-11-01T18:30:00.250-05:00",
+        "2024-11-01T18:30Z",
+        "2024-11-01T18:30+01:00",
+        "2024-02-29T00:00:00Z",
+        "2000-02-29T00:00:00Z",
+    ];
+
+    for value in valid {
+        let mut template = template();
+        template.relevant_date = Some(value.into());
+
+        assert!(invalid_dates(&template).is_empty(), "{}", value);
+    }
+}
+
+#[test]
+fn rejects_invalid_dates() {
+    let invalid = [
+        "",
+        "2024-11-01",
+        "2024-11-01T18:30:00",
+        "2024-11-01 18:30:00Z",
+        "2024-11-01T18:30:00+0100",
+        "2024-13-01T18:30:00Z",
+        "2023-02-29T18:30:00Z",
+        "1900-02-29T18:30:00Z",
+        "2024-11-01T24:00:00Z",
+        "2024-11-01T18:60:00Z",
+        "2024-11-01T18:30:00.Z",
+        "01.11.2024 18:30",
+    ];
+
+    for value in invalid {
+        let mut template = template();
+        template.relevant_date = Some(value.into());
+
+        assert_eq!(invalid_dates(&template), vec!["relevantDate"], "{}", value);
+    }
+}
+
+#[test]
+fn reports_every_invalid_date() {
+    let mut template = template();
+    template.expiration_date = Some("tomorrow".into());
+    template.relevant_date = Some("2024-11-01T18:30:00Z".into());
+    template.relevant_dates = Some(vec![RelevantDate::new_interval(
+        "2024-11-01T18:30:00Z",
+        "later",
+    )]);
+
+    let mut semantics = Semantics::new();
+    semantics.event_start_date = Some("soon".into());
+    template.semantics = Some(semantics);
+
+    let mut date_field = Field::new_string("date", "1. November");
+    date_field.date_style(EStyle::PkDateStyleMedium);
+    let mut details = Details::new();
+    details.add_primary_field(date_field);
+    details.add_secondary_field(Field::new_string("text", "not a date"));
+    template.generic(details);
+
+    assert_eq!(
+        invalid_dates(&template),
+        vec![
+            "expirationDate",
+            "relevantDates[0].endDate",
+            "semantics.eventStartDate",
+            "generic.primaryFields[0].value",
+        ]
+    );
+}
@@ -0,0 +1,165 @@
+//! Verification of signed pass archives
+
+mod common;
+
+use std::io::{Cursor, Read, Write};
+use wallet_pass::sign::{self, SignOptions, SigningIdentity, VerificationReport};
+use wallet_pass::Error;
+use zip::write::FileOptions;
+
+use common::KeyType;
+
+/// Sign a pass with an icon and a localization
+fn sign(identity: &SigningIdentity) -> Vec<u8> {
+    sign::sign_files(
+        vec![
+            ("icon.png".to_owned(), common::png(29, 29)),
+            (
+                "de.lproj/pass.strings".to_owned(),
+                b"\"a\" = \"b\";".to_vec(),
+            ),
+        ],
+        Some(&common::template()),
+        identity,
+        Cursor::new(Vec::new()),
+        &SignOptions::new(),
+    )
+    .unwrap()
+    .into_inner()
+}
+
+/// Files of an archive
+fn unzip(archive: &[u8]) -> Vec<(String, Vec<u8>)> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(archive)).unwrap();
+    let mut files = Vec::new();
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index).unwrap();
+        if file.is_dir() {
+            continue;
+        }
+        let mut content = Vec::new();
+        file.read_to_end(&mut content).unwrap();
+        files.push((file.name().to_owned(), content));
+    }
+
+    files
+}
+
+/// Archive of the given files
+fn zip(files: &[(String, Vec<u8>)]) -> Vec<u8> {
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, content) in files {
+        zip.start_file(name, FileOptions::default()).unwrap();
+        zip.write_all(content).unwrap();
+    }
+
+    zip.finish().unwrap().into_inner()
+}
+
+/// Sign a pass, change its files and verify it against the wwdr intermediate certificate
+fn verify_modified<F>(modify: F) -> VerificationReport
+where
+    F: FnOnce(&mut Vec<(String, Vec<u8>)>),
+{
+    let (identity, ca) = common::identity(KeyType::Rsa);
+    let mut files = unzip(&sign(&identity));
+    modify(&mut files);
+
+    sign::verify(Cursor::new(zip(&files)), &[ca]).unwrap()
+}
+
+/// Content of a file of the pass
+fn content<'a>(files: &'a mut [(String, Vec<u8>)], name: &str) -> &'a mut Vec<u8> {
+    files
+        .iter_mut()
+        .find(|(file_name, _)| file_name == name)
+        .map(|(_, content)| content)
+        .unwrap()
+}
+
+#[test]
+fn valid_pass() {
+    let (identity, ca) = common::identity(KeyType::Rsa);
+
+    let report = sign::verify(Cursor::new(sign(&identity)), &[ca]).unwrap();
+
+    assert!(report.is_valid(), "{:?}", report);
+    assert_eq!(
+        report.signer_certificate.unwrap().to_der().unwrap(),
+        sign::ManifestSigner::certificate(&identity)
+            .to_der()
+            .unwrap()
+    );
+}
+
+#[test]
+fn missing_file() {
+    let report = verify_modified(|files| files.retain(|(name, _)| name != "icon.png"));
+
+    assert!(!report.is_valid());
+    assert_eq!(report.missing_files, vec!["icon.png"]);
+    assert!(report.extra_files.is_empty());
+    assert!(report.modified_files.is_empty());
+    assert!(report.signature_error.is_none());
+}
+
+#[test]
+fn extra_file() {
+    let report = verify_modified(|files| files.push(("logo.png".into(), common::png(1, 1))));
+
+    assert!(!report.is_valid());
+    assert_eq!(report.extra_files, vec!["logo.png"]);
+    assert!(report.missing_files.is_empty());
+    assert!(report.modified_files.is_empty());
+}
+
+#[test]
+fn modified_file() {
+    let report = verify_modified(|files| content(files, "de.lproj/pass.strings").push(b' '));
+
+    assert!(!report.is_valid());
+    assert_eq!(report.modified_files, vec!["de.lproj/pass.strings"]);
+    assert!(report.missing_files.is_empty());
+    assert!(report.extra_files.is_empty());
+    assert!(report.signature_error.is_none());
+}
+
+#[test]
+fn modified_manifest() {
+    let report = verify_modified(|files| content(files, "manifest.json").push(b'\n'));
+
+    assert!(!report.is_valid());
+    assert!(report.signature_error.is_some());
+    assert!(report.modified_files.is_empty());
+}
+
+#[test]
+fn missing_signature() {
+    let report = verify_modified(|files| files.retain(|(name, _)| name != "signature"));
+
+    assert!(!report.is_valid());
+    assert!(report.signature_error.is_some());
+}
+
+#[test]
+fn untrusted_certificate() {
+    let (identity, _) = common::identity(KeyType::Rsa);
+    let (_, other_ca) = common::identity(KeyType::Rsa);
+
+    let report = sign::verify(Cursor::new(sign(&identity)), &[other_ca]).unwrap();
+
+    assert!(!report.is_valid());
+    assert!(report.signature_error.is_some());
+    assert!(report.signer_certificate.is_none());
+}
+
+#[test]
+fn missing_manifest() {
+    let (identity, ca) = common::identity(KeyType::Rsa);
+    let mut files = unzip(&sign(&identity));
+    files.retain(|(name, _)| name != "manifest.json");
+
+    let result = sign::verify(Cursor::new(zip(&files)), &[ca]);
+
+    assert!(matches!(result, Err(Error::MissingManifest)));
+}